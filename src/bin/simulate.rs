@@ -0,0 +1,229 @@
+// Headless balance testing.  Plays many seeded games with computer
+// captains, as fast as possible, and reports how they went.
+
+extern crate rust_seawar;
+use rust_seawar::captain::{Ai, Captain, Random};
+use rust_seawar::console::{self, Silent};
+use rust_seawar::difficulty::Difficulty;
+use rust_seawar::mission::Mission;
+use rust_seawar::rules::{rules, set_rules, Rules};
+use rust_seawar::{ocean, parse_size, play, seed, set_fast, set_ocean, Cause, Ocean, Outcome,
+                  Settings};
+use rust_seawar::{MAX_PLAYERS, MAX_SIZE, MIN_SIZE};
+
+use std::collections::HashMap;
+use std::{env, process};
+
+struct Options {
+    games: u64,
+    seed: u64,
+    subs: usize,
+    difficulty: Difficulty,
+    mission: Mission,
+    random: bool,
+}
+
+fn usage() -> ! {
+    println!("Usage: simulate [--games N] [--seed N] [--subs N] [--size WIDTHxHEIGHT] [--wrap]");
+    println!("                [--difficulty SPEC] [--rules FILE] [--classic]");
+    println!("                [--mission SPEC] [--turns N] [--sink N] [--captain ai|random]");
+    println!("  --games  Number of games to play (default 1000)");
+    println!("  --seed   Seed of the first game, the rest follow on (default 1)");
+    println!("  --subs   Computer captains per game, 1 to {} (default 1)", MAX_PLAYERS);
+    println!("  --captain  Who commands them: ai, or random orders for a baseline (default ai)");
+    println!("  --size   Size of the ocean, {} to {} cells each way (default 20x20)",
+             MIN_SIZE, MAX_SIZE);
+    println!("  --wrap   The ocean wraps around at the edges");
+    println!("  --mission  What it takes to win and lose, as in rust_seawar --help");
+    println!("  --turns  Same as --mission turns=N");
+    println!("  --sink   Same as --mission sink=N");
+    println!("  --rules  Play by the house rules in FILE");
+    println!("  --classic  Play by the original BASIC game's rules where they differ");
+    println!("  --difficulty  A preset, cadet to admiral, with any changes, e.g. captain,repair=2");
+    process::exit(1);
+}
+
+fn parse_args() -> Options {
+    let mut options = Options { games: 1000, seed: 1, subs: 1, difficulty: Difficulty::default(),
+                                mission: Mission::default(), random: false };
+    let mut classic = false;
+    let mut mission = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => match args.next().and_then(|n| n.parse::<u64>().ok()) {
+                Some(n) if n > 0 => options.games = n,
+                _ => usage(),
+            },
+            "--seed" => match args.next().and_then(|n| n.parse::<u64>().ok()) {
+                Some(n) => options.seed = n,
+                None => usage(),
+            },
+            "--subs" => match args.next().and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) if (1..=MAX_PLAYERS).contains(&n) => options.subs = n,
+                _ => usage(),
+            },
+            "--captain" => match args.next().as_deref() {
+                Some("ai") => options.random = false,
+                Some("random") => options.random = true,
+                _ => usage(),
+            },
+            "--size" => match parse_size(args.next()) {
+                Some(o) => set_ocean(o),
+                None => usage(),
+            },
+            "--wrap" => set_ocean(Ocean { wrap: true, ..ocean() }),
+            "--difficulty" => match Difficulty::parse(&args.next().unwrap_or_default()) {
+                Ok(d) => options.difficulty = d,
+                Err(e) => {
+                    println!("{}", e);
+                    usage();
+                }
+            },
+            "--rules" => match args.next().map(|path| Rules::load(path.as_ref())) {
+                Some(Ok(rules)) => set_rules(rules),
+                Some(Err(e)) => {
+                    println!("{}", e);
+                    process::exit(1);
+                }
+                None => usage(),
+            },
+            "--classic" => classic = true,
+            "--mission" => match args.next() {
+                Some(spec) => mission.push(spec),
+                None => usage(),
+            },
+            "--turns" | "--sink" => match args.next() {
+                Some(n) => mission.push(format!("{}={}", &arg[2..], n)),
+                None => usage(),
+            },
+            _ => usage(),
+        }
+    }
+    match Mission::parse(&mission.join(",")) {
+        Ok(mission) => options.mission = mission,
+        Err(e) => {
+            println!("{}", e);
+            usage();
+        }
+    }
+    if classic {
+        set_rules(Rules { classic, ..rules() });
+    }
+    options
+}
+
+// Running totals of a resource, by turn.
+#[derive(Default)]
+struct Curve {
+    total: Vec<u64>,
+    count: Vec<u64>,
+}
+
+impl Curve {
+    fn add(&mut self, turn: usize, value: u32) {
+        if self.total.len() <= turn {
+            self.total.resize(turn + 1, 0);
+            self.count.resize(turn + 1, 0);
+        }
+        self.total[turn] += value as u64;
+        self.count[turn] += 1;
+    }
+
+    fn average(&self, turn: usize) -> u64 {
+        self.total[turn] / self.count[turn]
+    }
+}
+
+#[derive(Default)]
+struct Tally {
+    games: u64,
+    wins: u64,
+    turns: u64,
+    ships_sunk: u64,
+    subs: u64,
+    deaths: HashMap<Cause, u64>,
+    power: Curve,
+    fuel: Curve,
+    torpedos: Curve,
+    missiles: Curve,
+    crew: Curve,
+}
+
+impl Tally {
+    fn add(&mut self, outcome: &Outcome) {
+        self.games += 1;
+        self.turns += outcome.turns as u64;
+        if outcome.won() {
+            self.wins += 1;
+        }
+        for pi in &outcome.players {
+            self.subs += 1;
+            self.ships_sunk += pi.ships_sunk as u64;
+            if let Some(cause) = pi.cause {
+                *self.deaths.entry(cause).or_insert(0) += 1;
+            }
+            for (turn, s) in pi.history.iter().enumerate() {
+                self.power.add(turn, s.power);
+                self.fuel.add(turn, s.fuel);
+                self.torpedos.add(turn, s.torpedos);
+                self.missiles.add(turn, s.missiles);
+                self.crew.add(turn, s.crew);
+            }
+        }
+    }
+
+    fn report(&self) {
+        let percent = |n: u64, of: u64| 100. * n as f64 / of as f64;
+        println!("Games played:      {}", self.games);
+        println!("Win rate:          {:.1}%", percent(self.wins, self.games));
+        println!("Average turns:     {:.1}", self.turns as f64 / self.games as f64);
+        println!("Ships sunk per sub: {:.1}", self.ships_sunk as f64 / self.subs as f64);
+        println!();
+        println!("Causes of death ({} subs):", self.subs);
+        let mut deaths: Vec<_> = self.deaths.iter().collect();
+        deaths.sort_by_key(|d| std::cmp::Reverse(*d.1));
+        for (cause, n) in deaths {
+            println!("    {:16} {:6} {:5.1}%", format!("{:?}", cause), n, percent(*n, self.subs));
+        }
+        let lost: u64 = self.deaths.values().sum();
+        println!("    {:16} {:6} {:5.1}%", "Survived", self.subs - lost,
+                 percent(self.subs - lost, self.subs));
+        println!();
+        println!("Average supplies of subs still afloat, by turn:");
+        println!("    TURN  AFLOAT  POWER   FUEL  TORPEDOS  MISSILES  CREW");
+        let turns = self.power.total.len();
+        let mut turn = 0;
+        while turn < turns {
+            println!("    {:4}  {:6}  {:5}  {:5}  {:8}  {:8}  {:4}",
+                     turn + 1, self.power.count[turn],
+                     self.power.average(turn), self.fuel.average(turn),
+                     self.torpedos.average(turn), self.missiles.average(turn),
+                     self.crew.average(turn));
+            // Every turn early on, then more sparsely.
+            turn += if turn < 10 { 1 } else if turn < 50 { 5 } else { 25 };
+        }
+    }
+}
+
+fn main() {
+    let options = parse_args();
+    set_fast(true);
+    let mut tally = Tally::default();
+    let settings = Settings { difficulty: options.difficulty.clone(),
+                              mission: options.mission.clone(),
+                              ..Settings::default() };
+    for game in 0..options.games {
+        console::install(vec![Box::new(Silent)]);
+        seed(options.seed.wrapping_add(game));
+        let mut captains: Vec<Box<dyn Captain>> = (0..options.subs)
+            .map(|id| if options.random {
+                Box::new(Random::new(&format!("Random {}", id + 1))) as Box<dyn Captain>
+            } else {
+                Box::new(Ai::new(id)) as Box<dyn Captain>
+            })
+            .collect();
+        tally.add(&play(&mut captains, &settings));
+    }
+    tally.report();
+}
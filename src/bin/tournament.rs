@@ -0,0 +1,174 @@
+// Pit strategies against each other.  Every entrant plays the same seeded
+// games, alone in the same ocean, and they're ranked on average score.
+//
+// An entrant is either a built-in strategy (ai or random) or the command
+// line of a program speaking the bot protocol, which is started afresh for
+// every game.
+
+extern crate rust_seawar;
+use rust_seawar::bot::Bot;
+use rust_seawar::captain::{Ai, Captain, Random};
+use rust_seawar::console::{self, Silent};
+use rust_seawar::{ocean, parse_size, play, seed, set_fast, set_ocean, Ocean, Outcome,
+                  Settings};
+use rust_seawar::{MAX_SIZE, MIN_SIZE};
+
+use std::env;
+use std::io::BufReader;
+use std::process::{self, Child, Stdio};
+
+struct Options {
+    games: u64,
+    seed: u64,
+    entrants: Vec<String>,
+}
+
+fn usage() -> ! {
+    println!("Usage: tournament [--games N] [--seed N] [--size WIDTHxHEIGHT] [--wrap] ENTRANT...");
+    println!("  --games  Number of games each entrant plays (default 100)");
+    println!("  --seed   Seed of the first game, the rest follow on (default 1)");
+    println!("  --size   Size of the ocean, {} to {} cells each way (default 20x20)",
+             MIN_SIZE, MAX_SIZE);
+    println!("  --wrap   The ocean wraps around at the edges");
+    println!("ENTRANT is ai, random, or the command to run a bot, e.g. \"python3 bot.py\"");
+    process::exit(1);
+}
+
+fn parse_args() -> Options {
+    let mut options = Options { games: 100, seed: 1, entrants: Vec::new() };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => match args.next().and_then(|n| n.parse::<u64>().ok()) {
+                Some(n) if n > 1 => options.games = n,
+                _ => usage(),
+            },
+            "--seed" => match args.next().and_then(|n| n.parse::<u64>().ok()) {
+                Some(n) => options.seed = n,
+                None => usage(),
+            },
+            "--size" => match parse_size(args.next()) {
+                Some(o) => set_ocean(o),
+                None => usage(),
+            },
+            "--wrap" => set_ocean(Ocean { wrap: true, ..ocean() }),
+            _ if arg.starts_with("--") => usage(),
+            _ => options.entrants.push(arg),
+        }
+    }
+    if options.entrants.is_empty() {
+        usage();
+    }
+    options
+}
+
+// Ships sunk, with a bonus for clearing the ocean and surviving.
+fn score(outcome: &Outcome) -> f64 {
+    let pi = &outcome.players[0];
+    let mut score = pi.ships_sunk as f64;
+    if outcome.won() {
+        score += 10.;
+    }
+    if pi.alive {
+        score += 5.;
+    }
+    score
+}
+
+// Play one game with the entrant at the helm.
+fn play_game(entrant: &str) -> Outcome {
+    let mut child: Option<Child> = None;
+    let captain: Box<dyn Captain> = match entrant {
+        "ai" => Box::new(Ai::new(0)),
+        "random" => Box::new(Random::new("Random")),
+        _ => {
+            let mut words = entrant.split_whitespace();
+            let program = words.next().unwrap_or_else(|| usage());
+            let mut bot = process::Command::new(program)
+                .args(words)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap_or_else(|e| {
+                    println!("Unable to start {}: {}", entrant, e);
+                    process::exit(1);
+                });
+            let captain = Bot::new(BufReader::new(bot.stdout.take().unwrap()),
+                                   bot.stdin.take().unwrap());
+            console::install(vec![Box::new(captain.console())]);
+            child = Some(bot);
+            Box::new(captain)
+        }
+    };
+    if child.is_none() {
+        console::install(vec![Box::new(Silent)]);
+    }
+    let mut captains = vec![captain];
+    let outcome = play(&mut captains, &Settings::default());
+    // Closes the bot's input, which should be enough for it to quit.
+    drop(captains);
+    console::install(vec![Box::new(Silent)]);
+    if let Some(mut bot) = child {
+        bot.wait().ok();
+    }
+    outcome
+}
+
+struct Standing {
+    entrant: String,
+    scores: Vec<f64>,
+    wins: u64,
+    turns: u64,
+}
+
+impl Standing {
+    fn mean(&self) -> f64 {
+        self.scores.iter().sum::<f64>() / self.scores.len() as f64
+    }
+
+    // Half the width of the 95% confidence interval of the mean.
+    fn margin(&self) -> f64 {
+        let n = self.scores.len() as f64;
+        let mean = self.mean();
+        let variance = self.scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.);
+        1.96 * (variance / n).sqrt()
+    }
+}
+
+fn main() {
+    let options = parse_args();
+    set_fast(true);
+    let mut standings: Vec<Standing> = options.entrants.iter().map(|entrant| {
+        let mut standing = Standing {
+            entrant: entrant.clone(),
+            scores: Vec::new(),
+            wins: 0,
+            turns: 0,
+        };
+        for game in 0..options.games {
+            seed(options.seed.wrapping_add(game));
+            let outcome = play_game(entrant);
+            standing.scores.push(score(&outcome));
+            standing.turns += outcome.turns as u64;
+            if outcome.won() {
+                standing.wins += 1;
+            }
+        }
+        standing
+    }).collect();
+    standings.sort_by(|a, b| b.mean().partial_cmp(&a.mean()).unwrap());
+
+    println!("{} games each, seeds {} to {}", options.games, options.seed,
+             options.seed.wrapping_add(options.games - 1));
+    println!();
+    println!("RANK  ENTRANT                   SCORE  95% CI          WINS   TURNS");
+    for (rank, s) in standings.iter().enumerate() {
+        let (mean, margin) = (s.mean(), s.margin());
+        println!("{:4}  {:24} {:6.2}  {:6.2}-{:<6.2}  {:5.1}%  {:6.1}",
+                 rank + 1, s.entrant, mean, mean - margin, mean + margin,
+                 100. * s.wins as f64 / options.games as f64,
+                 s.turns as f64 / options.games as f64);
+    }
+    println!();
+    println!("Score is ships sunk, plus 10 for winning and 5 for surviving.");
+}
@@ -0,0 +1,278 @@
+// Machine play.  A bot, written in any language, commands a sub by
+// exchanging lines of text with the game, much like a chess engine talks
+// UCI.
+//
+// At the start of the game:
+//   seawar 1 ocean WIDTH HEIGHT wrap|nowrap
+// and the bot answers with
+//   name NAME
+//
+// Whenever orders are wanted, the game sends the state of the sub, one
+// line per item, then asks with go:
+//   turn N
+//   ships N                 enemy ships left to sink
+//   position X Y
+//   depth N
+//   power N
+//   fuel N
+//   torpedos N
+//   missiles N
+//   crew N
+//   resupplies N
+//   sunk N                  ships sunk so far
+//   damage engines H sonar H ...   negative health is under repair
+//   contact X Y TYPE        one per contact from the last sonar ping,
+//                           TYPE is sub, island, ship, mine, hq or monster
+//   go
+//
+// The bot answers with one order, every argument given, in the same words
+// as at the console:
+//   navigate DIRECTION POWER
+//   sonar
+//   torpedo DIRECTION
+//   missile DIRECTION FUEL [confirm]
+//   manuever DEPTH
+//   status
+//   resupply
+//   sabotage MEN
+//   convert fuel|power AMOUNT   converts from fuel or from power
+//   surrender
+// Directions are keypad digits, 8 is north.  Words can be shortened, so
+// "nav 9 400" will do.  An order that can't be read gets "error REASON"
+// and another go; like everything else the game says, the reason is in the
+// language the game is played in.  Orders can still be refused by the crew,
+// or not end the turn, in which case the state is sent again.
+//
+// Everything the game says in the meantime comes as
+//   info TEXT
+// and once the game is over
+//   over won|lost turns N sunk N [cause CAUSE]
+
+use std::cell::RefCell;
+use std::io::{BufRead, Write};
+use std::rc::Rc;
+
+use captain::{Captain, Observation};
+use console::Console;
+use super::{ocean, Cause, Command, EType, Order, Outcome};
+
+fn cause_name(cause: Cause) -> &'static str {
+    match cause {
+        Cause::Mine => "mine",
+        Cause::Monster => "monster",
+        Cause::Rammed => "rammed",
+        Cause::Supercritical => "supercritical",
+        Cause::PowerExhausted => "power-exhausted",
+        Cause::Retaliation => "retaliation",
+        Cause::Crushed => "crushed",
+        Cause::Misfire => "misfire",
+        Cause::FriendlyFire => "friendly-fire",
+        Cause::Surrendered => "surrendered",
+    }
+}
+
+fn contact_name(etype: &EType) -> &'static str {
+    match *etype {
+        EType::Player(_) => "sub",
+        EType::Island => "island",
+        EType::Ship => "ship",
+        EType::Mine => "mine",
+        EType::HQ => "hq",
+        EType::Monster => "monster",
+    }
+}
+
+/// Read an order in the bot protocol.  This is the same as at the console,
+/// abbreviations and all, except that every argument must be given.
+pub fn parse_order(line: &str) -> Result<Order, String> {
+    let mut order = super::parse_order(line)?;
+    let missing = match order.command {
+        Command::Navigate | Command::Torpedo | Command::Missile
+            if order.direction.is_none() => "missing.direction",
+        Command::Navigate if order.amount.is_none() => "missing.power",
+        Command::Missile if order.amount.is_none() => "missing.fuel",
+        Command::Manuever if order.depth.is_none() => "missing.depth",
+        Command::Sabotage if order.amount.is_none() => "missing.men",
+        Command::Convert if order.conversion.is_none() => "missing.conversion",
+        Command::Convert if order.amount.is_none() => "missing.amount",
+        _ => {
+            // No confirmation means don't fire from an unsafe depth.
+            order.confirm = order.confirm.or(Some(false));
+            return Ok(order);
+        }
+    };
+    Err(tr!(missing))
+}
+
+/// Game text for a bot, a line at a time as info.
+pub struct Tagged<W: Write> {
+    writer: Rc<RefCell<W>>,
+    partial: String,
+}
+
+impl<W: Write> Console for Tagged<W> {
+    fn write(&mut self, text: &str) {
+        for c in text.chars() {
+            // Returns only come from animations, which can go.
+            if c == '\n' || c == '\r' {
+                if !self.partial.trim().is_empty() {
+                    writeln!(self.writer.borrow_mut(), "info {}", self.partial.trim_end()).ok();
+                }
+                self.partial.clear();
+            } else {
+                self.partial.push(c);
+            }
+        }
+        self.writer.borrow_mut().flush().ok();
+    }
+
+    // Bots never get asked questions.
+    fn read_line(&mut self) -> Option<String> {
+        None
+    }
+}
+
+/// A captain at the other end of the bot protocol.  Should the bot go
+/// quiet, its sub surrenders.
+pub struct Bot<R: BufRead, W: Write> {
+    reader: R,
+    writer: Rc<RefCell<W>>,
+    gone: bool,
+}
+
+impl<R: BufRead, W: Write> Bot<R, W> {
+    pub fn new(reader: R, writer: W) -> Bot<R, W> {
+        Bot { reader, writer: Rc::new(RefCell::new(writer)), gone: false }
+    }
+
+    /// A console for what the game says to this bot.
+    pub fn console(&self) -> Tagged<W> {
+        Tagged { writer: self.writer.clone(), partial: String::new() }
+    }
+
+    fn send(&mut self, line: &str) {
+        let mut writer = self.writer.borrow_mut();
+        if writeln!(writer, "{}", line).and_then(|_| writer.flush()).is_err() {
+            self.gone = true;
+        }
+    }
+
+    fn receive(&mut self) -> Option<String> {
+        if self.gone {
+            return None;
+        }
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) | Err(_) => {
+                self.gone = true;
+                None
+            }
+            Ok(_) => Some(line.trim().to_string()),
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> Captain for Bot<R, W> {
+    fn name(&mut self) -> String {
+        let o = ocean();
+        self.send(&format!("seawar 1 ocean {} {} {}",
+                           o.width, o.height, if o.wrap { "wrap" } else { "nowrap" }));
+        loop {
+            let line = match self.receive() {
+                Some(line) => line,
+                None => return "Bot".to_string(),
+            };
+            match line.strip_prefix("name ") {
+                Some(name) if !name.trim().is_empty() => return name.trim().to_string(),
+                _ => self.send("error expected name NAME"),
+            }
+        }
+    }
+
+    fn orders(&mut self, obs: &Observation) -> Order {
+        let pi = obs.info;
+        self.send(&format!("turn {}", obs.turn));
+        self.send(&format!("ships {}", obs.ships));
+        self.send(&format!("position {} {}", obs.position.x, obs.position.y));
+        self.send(&format!("depth {}", pi.depth));
+        self.send(&format!("power {}", pi.power));
+        self.send(&format!("fuel {}", pi.fuel));
+        self.send(&format!("torpedos {}", pi.torpedos));
+        self.send(&format!("missiles {}", pi.missiles));
+        self.send(&format!("crew {}", pi.crew));
+        self.send(&format!("resupplies {}", pi.resupply_left));
+        self.send(&format!("sunk {}", pi.ships_sunk));
+        let damage: Vec<_> = pi.damage.iter()
+            .map(|(key, value)| format!("{} {:.2}", format!("{:?}", key).to_lowercase(), value))
+            .collect();
+        self.send(&format!("damage {}", damage.join(" ")));
+        if let Some(ref chart) = pi.last_sonar {
+            for (pos, etype) in chart.contacts() {
+                if pos != obs.position {
+                    self.send(&format!("contact {} {} {}", pos.x, pos.y, contact_name(&etype)));
+                }
+            }
+        }
+        loop {
+            self.send("go");
+            let line = match self.receive() {
+                Some(line) => line,
+                None => return Order::new(Command::Surrender),
+            };
+            match parse_order(&line) {
+                Ok(order) => return order,
+                Err(reason) => self.send(&format!("error {}", reason)),
+            }
+        }
+    }
+
+    fn game_over(&mut self, outcome: &Outcome, id: usize) {
+        let pi = &outcome.players[id];
+        let mut line = format!("over {} turns {} sunk {}",
+                               if outcome.won() { "won" } else { "lost" },
+                               outcome.turns, pi.ships_sunk);
+        if let Some(cause) = pi.cause {
+            line += &format!(" cause {}", cause_name(cause));
+        }
+        self.send(&line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Conversion;
+
+    #[test]
+    fn complete_orders() {
+        let order = parse_order("nav 9 400").unwrap();
+        assert_eq!(order.command, Command::Navigate);
+        assert_eq!(order.direction, Some((1, -1)));
+        assert_eq!(order.amount, Some(400));
+        // Missiles aren't fired from unsafe depths without confirmation.
+        assert_eq!(parse_order("missile 2 300").unwrap().confirm, Some(false));
+        assert_eq!(parse_order("missile 2 300 confirm").unwrap().confirm, Some(true));
+        let order = parse_order("convert power 200").unwrap();
+        assert_eq!(order.conversion, Some(Conversion::PowerToFuel));
+        assert_eq!(order.amount, Some(200));
+        assert_eq!(parse_order("manuever 250").unwrap().depth, Some(250));
+        for order in ["sonar", "status", "resupply", "surrender"].iter() {
+            assert!(parse_order(order).is_ok(), "{}", order);
+        }
+    }
+
+    #[test]
+    fn incomplete_orders() {
+        let error = |line| parse_order(line).unwrap_err();
+        assert_eq!(error("navigate"), "Missing direction");
+        assert_eq!(error("navigate 8"), "Missing power");
+        assert_eq!(error("torpedo"), "Missing direction");
+        assert_eq!(error("missile 8"), "Missing fuel");
+        assert_eq!(error("dive"), "Missing depth");
+        assert_eq!(error("sabotage"), "Missing men");
+        assert_eq!(error("convert"), "Missing fuel or power");
+        assert_eq!(error("convert fuel"), "Missing amount");
+        // Orders that can't be read at all say why, as at the console.
+        assert_eq!(error("fly 8"), "Unknown command 'fly'");
+    }
+}
@@ -0,0 +1,457 @@
+// Whoever gives the orders for a sub: a person at a console, or the
+// computer.
+
+use rand::Rng;
+use std::collections::{BTreeMap, VecDeque};
+
+use help;
+use super::{ocean, parse_order, prompt, reject_orders, with_rng, Command, Conversion, EType,
+            Order, Outcome, PlayerInfo, Position, SubSystem, COMMAND_WORDS};
+use rules::rules;
+
+/// What a captain knows when deciding on orders.  Sonar results are in
+/// info.last_sonar, which may be from an earlier turn.
+pub struct Observation<'a> {
+    pub info: &'a PlayerInfo,
+    pub position: Position,
+    pub turn: u32,
+    // Enemy ships left to sink.
+    pub ships: u32,
+}
+
+pub trait Captain {
+    fn name(&mut self) -> String;
+
+    // Called until an order ends the turn.
+    fn orders(&mut self, obs: &Observation) -> Order;
+
+    // Automated captains get a limited number of orders per turn, in case
+    // they keep giving ones that don't end it.
+    fn automated(&self) -> bool {
+        true
+    }
+
+    // Told how the game ended.  outcome.players[id] was this captain's sub.
+    fn game_over(&mut self, _outcome: &Outcome, _id: usize) {}
+}
+
+// Queued orders are held when anything comes within this many cells.
+const ALERT_RANGE: f32 = 3.;
+// Or when the power gets this low.
+const LOW_POWER: u32 = 1000;
+
+/// Someone at a console.  Besides giving orders one at a time, they can
+/// line up several, and save lists of orders as macros.
+pub struct Human {
+    question: String,
+    // Asked once, and kept for the rest of the session.
+    name: Option<String>,
+    macros: BTreeMap<String, Vec<String>>,
+    queue: VecDeque<(String, Order)>,
+}
+
+impl Human {
+    pub fn new(question: &str) -> Human {
+        Human {
+            question: question.to_string(),
+            name: None,
+            macros: BTreeMap::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    // Orders separated by ;, with macros replaced by what they stand for.
+    fn expand(&self, input: &str) -> Result<Vec<(String, Order)>, String> {
+        let mut orders = Vec::new();
+        for text in input.split(';').map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            match self.macros.get(&text.to_lowercase()) {
+                Some(texts) => for text in texts {
+                    orders.push((text.clone(), parse_order(text)?));
+                },
+                None => orders.push((text.to_string(), parse_order(text)?)),
+            }
+        }
+        if orders.is_empty() {
+            return Err(tr!("order.none"));
+        }
+        Ok(orders)
+    }
+
+    // macro NAME ORDERS saves them, macro NAME forgets them, and macro on
+    // its own lists them all.
+    fn define(&mut self, args: &str) {
+        let args = args.trim();
+        let (name, body) = match args.find(char::is_whitespace) {
+            Some(i) => (args[..i].to_lowercase(), args[i..].trim()),
+            None => (args.to_lowercase(), ""),
+        };
+        if name.is_empty() {
+            if self.macros.is_empty() {
+                outln!("{}", tr!("macro.none"));
+            }
+            for (name, texts) in &self.macros {
+                outln!("    {:12} {}", name, texts.join("; "));
+            }
+        } else if COMMAND_WORDS.iter().any(|w| w.0.starts_with(&name)) ||
+            name.parse::<i32>().is_ok() || name == "macro" || name == "help" {
+            outln!("{}", tr!("macro.command", name));
+        } else if body.is_empty() {
+            if self.macros.remove(&name).is_some() {
+                outln!("{}", tr!("macro.forgotten", name));
+            }
+        } else {
+            match self.expand(body) {
+                Ok(orders) => {
+                    let texts = orders.into_iter().map(|o| o.0).collect();
+                    self.macros.insert(name.clone(), texts);
+                    outln!("{}", tr!("macro.saved", name));
+                }
+                Err(reason) => reject_orders(body, &reason),
+            }
+        }
+    }
+
+    // Reasons to stop and let the captain think again.
+    fn alert(obs: &Observation) -> Option<String> {
+        let pi = obs.info;
+        if pi.under_fire {
+            return Some(tr!("alert.attack"));
+        }
+        if pi.power < LOW_POWER {
+            return Some(tr!("alert.power", pi.power));
+        }
+        let contacts = pi.last_sonar.as_ref().map_or(Vec::new(), |chart| chart.contacts());
+        contacts.iter()
+            .filter(|c| c.0 != obs.position && c.0.in_range(&obs.position, ALERT_RANGE))
+            .find(|c| matches!(c.1, EType::Ship | EType::Monster | EType::Player(_)))
+            .map(|c| tr!("alert.contact", format!("{:?}", c.0)))
+    }
+}
+
+impl Captain for Human {
+    fn name(&mut self) -> String {
+        let question = &self.question;
+        self.name.get_or_insert_with(|| prompt(question)).clone()
+    }
+
+    fn orders(&mut self, obs: &Observation) -> Order {
+        if !self.queue.is_empty() {
+            if let Some(reason) = Human::alert(obs) {
+                outln!("{}", tr!("alert.holding", reason, self.queue.len(), obs.info.name));
+                self.queue.clear();
+            }
+        }
+        let (text, mut order) = match self.queue.pop_front() {
+            Some(queued) => queued,
+            None => loop {
+                let input = prompt(&tr!("prompt.orders", obs.info.name));
+                let mut words = input.trim_start().splitn(2, char::is_whitespace);
+                let first = words.next().unwrap_or("").to_lowercase();
+                if first == "macro" {
+                    self.define(words.next().unwrap_or(""));
+                    continue;
+                }
+                if first == "help" || first == "?" {
+                    help::show(words.next().unwrap_or(""));
+                    continue;
+                }
+                match self.expand(&input) {
+                    Ok(orders) => {
+                        self.queue.extend(orders);
+                        break self.queue.pop_front().unwrap();
+                    }
+                    Err(reason) => reject_orders(&input, &reason),
+                }
+            },
+        };
+        if !self.queue.is_empty() {
+            outln!("{}", tr!("queued", text, self.queue.len()));
+        }
+        order.interactive = true;
+        order
+    }
+
+    fn automated(&self) -> bool {
+        false
+    }
+
+    // Orders still queued were for the game just ended.
+    fn game_over(&mut self, _outcome: &Outcome, _id: usize) {
+        self.queue.clear();
+    }
+}
+
+const AI_NAMES: [&str; 4] = ["Nemo", "Ramius", "Ahab", "Dahl"];
+
+// Keypad order, 1 to 9 without 5.
+const DIRECTIONS: [(i8, i8); 8] = [(-1, 1), (0, 1), (1, 1), (-1, 0), (1, 0), (-1, -1), (0, -1), (1, -1)];
+
+// Torpedos travel at least this far, depending on depth.
+const SHALLOW_REACH: i32 = 3;
+const DEEP_REACH: i32 = 8;
+// Farthest the AI will move in one turn.  More than 10 risks the pile.
+const MAX_STEPS: i32 = 3;
+// Depth the AI prefers: deep enough for long torpedo runs.
+const CRUISING_DEPTH: i32 = 100;
+
+/// A reference computer captain.  It pings sonar every turn, torpedos any
+/// ship lined up within reach, otherwise closes in on the nearest ship, and
+/// heads home to resupply when running low.
+pub struct Ai {
+    name: String,
+    hq: Option<Position>,
+    turn: u32,
+    asked: u32,
+}
+
+impl Ai {
+    pub fn new(id: usize) -> Ai {
+        Ai {
+            name: AI_NAMES[id % AI_NAMES.len()].to_string(),
+            hq: None,
+            turn: 0,
+            asked: 0,
+        }
+    }
+
+    fn running_low(pi: &PlayerInfo) -> bool {
+        pi.torpedos < 3 || pi.power < 2500 || pi.crew < 20
+    }
+
+    fn can_navigate(pi: &PlayerInfo) -> bool {
+        pi.damage[SubSystem::Engines] >= 0. && pi.crew >= rules().navigate_crew &&
+            pi.power > rules().power_per_move
+    }
+
+    fn can_manuever(pi: &PlayerInfo) -> bool {
+        pi.damage[SubSystem::Computers] >= 0. && pi.crew >= rules().manuever_crew
+    }
+
+    fn can_torpedo(pi: &PlayerInfo) -> bool {
+        pi.damage[SubSystem::Torpedos] >= 0. && pi.crew >= rules().torpedo_crew &&
+            pi.torpedos > 0 && pi.depth < rules().deep_depth
+    }
+
+    // Direction and distance to target, if it's on one of the eight
+    // compass lines and nothing is in the way.
+    fn line_of_fire(obs: &Observation, target: &Position) -> Option<((i8, i8), i32)> {
+        let (dx, dy) = obs.position.heading(target);
+        if !(dx == 0 || dy == 0 || dx.abs() == dy.abs()) {
+            return None;
+        }
+        let dir = (dx.signum() as i8, dy.signum() as i8);
+        let dist = dx.abs().max(dy.abs());
+        let chart = obs.info.last_sonar.as_ref()?;
+        let Position{mut x, mut y} = obs.position;
+        for _ in 1..dist {
+            let next = ocean().step(x, y, dir.0, dir.1)?;
+            x = next.0;
+            y = next.1;
+            if chart.contact(&Position {x, y}).is_some() {
+                return None;
+            }
+        }
+        Some((dir, dist))
+    }
+
+    // Where we'd end up moving steps in direction dir, if the way is clear
+    // of everything sonar can see, of monsters' reach, and of ships that
+    // might ram us.
+    fn safe_course(obs: &Observation, contacts: &[(Position, EType)],
+                   dir: (i8, i8), steps: i32) -> Option<Position> {
+        let chart = obs.info.last_sonar.as_ref()?;
+        let Position{mut x, mut y} = obs.position;
+        for _ in 0..steps {
+            let next = ocean().step(x, y, dir.0, dir.1)?;
+            x = next.0;
+            y = next.1;
+            if chart.contact(&Position {x, y}).is_some() {
+                return None;
+            }
+        }
+        let dest = Position {x, y};
+        let danger = contacts.iter().any(|(pos, etype)| match *etype {
+            EType::Monster => pos.in_range(&dest, 2.),
+            EType::Ship => pos.in_range(&dest, 1.),
+            _ => false,
+        });
+        if danger { None } else { Some(dest) }
+    }
+
+    // Best short move towards the targets.  Being lined up within torpedo
+    // reach of one of them beats simply being close.
+    fn close_in(&self, obs: &Observation, contacts: &[(Position, EType)],
+                targets: &[Position], reach: i32) -> Option<Order> {
+        let mut best: Option<(f32, Order)> = None;
+        for &dir in &DIRECTIONS {
+            for steps in 1..=MAX_STEPS {
+                if (steps as u32 + 1) * rules().power_per_move >= obs.info.power {
+                    break;
+                }
+                let dest = match Ai::safe_course(obs, contacts, dir, steps) {
+                    Some(dest) => dest,
+                    None => break,
+                };
+                let score = targets.iter().map(|t| {
+                    let (dx, dy) = dest.heading(t);
+                    let lined_up = dx == 0 || dy == 0 || dx.abs() == dy.abs();
+                    if lined_up && dx.abs().max(dy.abs()) <= reach {
+                        0.
+                    } else {
+                        dest.distance(t)
+                    }
+                }).fold(f32::MAX, f32::min);
+                if best.as_ref().is_none_or(|b| score < b.0) {
+                    let order = Order::new(Command::Navigate)
+                        .with_direction(dir)
+                        .with_amount(steps as u32 * rules().power_per_move);
+                    best = Some((score, order));
+                }
+            }
+        }
+        best.map(|b| b.1)
+    }
+
+    // Something that ends the turn without doing much.
+    fn hold(&self, pi: &PlayerInfo) -> Order {
+        if Ai::can_manuever(pi) {
+            Order::new(Command::Manuever).with_depth(pi.depth)
+        } else {
+            Order::new(Command::Status)
+        }
+    }
+}
+
+impl Captain for Ai {
+    fn name(&mut self) -> String {
+        self.name.clone()
+    }
+
+    fn orders(&mut self, obs: &Observation) -> Order {
+        let pi = obs.info;
+        if self.turn != obs.turn {
+            self.turn = obs.turn;
+            self.asked = 0;
+        }
+        self.asked += 1;
+        // Fresh picture every turn, it only costs 50 power.
+        if self.asked == 1 && pi.damage[SubSystem::Sonar] >= 0. && pi.crew >= rules().sonar_crew {
+            return Order::new(Command::Sonar);
+        }
+        // Orders that didn't work out are being given again.
+        if self.asked > 4 {
+            return self.hold(pi);
+        }
+
+        let contacts = match pi.last_sonar {
+            Some(ref chart) => chart.contacts(),
+            None => Vec::new(),
+        };
+        if let Some((pos, _)) = contacts.iter().find(|c| c.1 == EType::HQ) {
+            self.hq = Some(pos.clone());
+        }
+
+        // Head home for supplies
+        if Ai::running_low(pi) && pi.resupply_left > 0 &&
+            pi.damage[SubSystem::Resupply] >= 0. {
+            if let Some(hq) = self.hq.clone() {
+                if obs.position.distance(&hq) <= rules().docking_range {
+                    if pi.depth <= rules().shallow_depth {
+                        return Order::new(Command::Resupply);
+                    } else if Ai::can_manuever(pi) {
+                        return Order::new(Command::Manuever).with_depth(rules().shallow_depth);
+                    }
+                } else if Ai::can_navigate(pi) {
+                    if let Some(order) = self.close_in(obs, &contacts, &[hq], 0) {
+                        return order;
+                    }
+                }
+            }
+        }
+
+        // Stay deep enough for a decent torpedo run
+        if pi.depth <= rules().shallow_depth && Ai::can_manuever(pi) {
+            return Order::new(Command::Manuever).with_depth(CRUISING_DEPTH);
+        }
+        let reach = if pi.depth > rules().shallow_depth { DEEP_REACH } else { SHALLOW_REACH };
+
+        let ships: Vec<Position> = contacts.iter()
+            .filter(|c| c.1 == EType::Ship)
+            .map(|c| c.0.clone())
+            .collect();
+        if Ai::can_torpedo(pi) {
+            for ship in &ships {
+                if let Some((dir, dist)) = Ai::line_of_fire(obs, ship) {
+                    if dist <= reach {
+                        return Order::new(Command::Torpedo).with_direction(dir);
+                    }
+                }
+            }
+        }
+
+        if Ai::can_navigate(pi) && !ships.is_empty() {
+            if let Some(order) = self.close_in(obs, &contacts, &ships, reach) {
+                return order;
+            }
+        }
+        self.hold(pi)
+    }
+
+    // Headquarters moves between games, so forget where it was.
+    fn game_over(&mut self, _outcome: &Outcome, _id: usize) {
+        *self = Ai { name: self.name.clone(), hq: None, turn: 0, asked: 0 };
+    }
+}
+
+/// A baseline for comparing strategies: any order but surrender, with
+/// random details.
+pub struct Random {
+    name: String,
+}
+
+impl Random {
+    pub fn new(name: &str) -> Random {
+        Random { name: name.to_string() }
+    }
+}
+
+impl Captain for Random {
+    fn name(&mut self) -> String {
+        self.name.clone()
+    }
+
+    fn orders(&mut self, obs: &Observation) -> Order {
+        let pi = obs.info;
+        with_rng(|r| {
+            let dir = DIRECTIONS[r.gen_range(0, DIRECTIONS.len())];
+            match r.gen_range(0, 9) {
+                0 => Order::new(Command::Navigate)
+                    .with_direction(dir)
+                    .with_amount(r.gen_range(1, 11) * 100),
+                1 => Order::new(Command::Sonar),
+                2 => Order::new(Command::Torpedo).with_direction(dir),
+                3 => {
+                    let mut order = Order::new(Command::Missile)
+                        .with_direction(dir)
+                        .with_amount(r.gen_range(1, pi.fuel.max(1) + 1));
+                    order.confirm = Some(false);
+                    order
+                }
+                4 => Order::new(Command::Manuever).with_depth(r.gen_range(0, 30) * 50),
+                5 => Order::new(Command::Status),
+                6 => Order::new(Command::Resupply),
+                7 => Order::new(Command::Sabotage).with_amount(r.gen_range(1, 11)),
+                _ => {
+                    let mut order = Order::new(Command::Convert)
+                        .with_amount(r.gen_range(1, 11) * 100);
+                    order.conversion = Some(if r.gen() {
+                        Conversion::FuelToPower
+                    } else {
+                        Conversion::PowerToFuel
+                    });
+                    order
+                }
+            }
+        })
+    }
+}
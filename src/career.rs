@@ -0,0 +1,302 @@
+// Careers.  A captain's service carries over from game to game: rank
+// earned through victories and points, medals for feats of arms, and a
+// record of recent actions.  Rank brings a bigger issue of supplies.
+//
+// Careers are kept in a text file, tab separated, a block per captain:
+//   captain NAME
+//   totals  GAMES WINS SCORE BEST SHIPS
+//   medal   NAME COUNT               one line per medal
+//   game    won|lost TURNS SHIPS SCORE   most recent last
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use debrief::Weapon;
+use score::score;
+use super::{Cause, Outcome, Supplies};
+
+// Ranks in order, by message key, with the victories and total points
+// needed for each.  New captains start as ensigns, and run up debts at
+// their peril.
+const RANKS: [(&str, u32, i64); 8] = [
+    ("rank.deck_scrubber", 0, i64::MIN),
+    ("rank.ensign", 0, 0),
+    ("rank.lieutenant", 1, 2000),
+    ("rank.lieutenant_commander", 3, 6000),
+    ("rank.commander", 5, 12000),
+    ("rank.captain", 10, 25000),
+    ("rank.commodore", 20, 50000),
+    ("rank.admiral", 35, 100000),
+];
+
+// Actions kept in the service record.
+const RECORD_LENGTH: usize = 20;
+
+// Whether sub id earned a medal in the game.
+type Feat = fn(&Outcome, usize) -> bool;
+
+// Medals, as named in the career file, the message key for their name and
+// what they're for, and whether a game earns one.
+const MEDALS: [(&str, &str, Feat); 6] = [
+    ("Navy Cross", "medal.navy_cross", |o, id| {
+        o.won() && o.players[id].alive
+    }),
+    ("Silver Star", "medal.silver_star", |o, id| {
+        o.players[id].ships_sunk >= 10
+    }),
+    ("Torpedo Ace", "medal.torpedo_ace", |o, id| {
+        o.players[id].record.sunk[Weapon::Torpedo] >= 5
+    }),
+    ("Saboteur's Star", "medal.saboteurs_star", |o, id| {
+        o.players[id].record.sunk[Weapon::Sabotage] >= 3
+    }),
+    ("Swift Victory", "medal.swift_victory", |o, id| {
+        o.won() && o.players[id].alive && o.turns <= 30
+    }),
+    ("Purple Heart", "medal.purple_heart", |o, id| {
+        let pi = &o.players[id];
+        !pi.alive && pi.cause != Some(Cause::Surrendered) && pi.ships_sunk > 0
+    }),
+];
+
+/// One captain's service.
+#[derive(Clone, Debug, Default)]
+pub struct Career {
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    pub score: i64,
+    pub best: i64,
+    pub ships: u32,
+    pub medals: Vec<(String, u32)>,
+    // (won, turns, ships, score), oldest first.
+    pub actions: Vec<(bool, u32, u32, i64)>,
+}
+
+// What a medal from the career file is called in the language in play.
+fn medal_name(medal: &str) -> String {
+    match MEDALS.iter().find(|m| m.0 == medal) {
+        Some(&(_, key, _)) => tr!(key),
+        None => medal.to_string(),
+    }
+}
+
+impl Career {
+    fn rank_index(&self) -> usize {
+        RANKS.iter()
+            .rposition(|&(_, wins, score)| self.wins >= wins && self.score >= score)
+            .unwrap_or(0)
+    }
+
+    pub fn rank(&self) -> String {
+        tr!(RANKS[self.rank_index()].0)
+    }
+
+    /// Supplies issued over the standard load, more for every rank above
+    /// ensign.
+    pub fn issue(&self) -> Supplies {
+        let r = self.rank_index().saturating_sub(1) as u32;
+        Supplies { power: 250 * r, fuel: 100 * r, torpedos: r / 2, missiles: r / 3, crew: r }
+    }
+
+    /// Enter sub id's part in the game in the record, announcing medals
+    /// and promotions.
+    pub fn serve(&mut self, outcome: &Outcome, id: usize) {
+        let pi = &outcome.players[id];
+        let points = score(outcome, id).total();
+        let won = outcome.won() && pi.alive;
+        let rank = self.rank_index();
+        self.games += 1;
+        if won {
+            self.wins += 1;
+        }
+        self.score += points;
+        self.best = if self.games == 1 { points } else { self.best.max(points) };
+        self.ships += pi.ships_sunk;
+        self.actions.push((won, outcome.turns, pi.ships_sunk, points));
+        if self.actions.len() > RECORD_LENGTH {
+            self.actions.remove(0);
+        }
+        for &(medal, key, earned) in MEDALS.iter() {
+            if earned(outcome, id) {
+                outln!("{}", tr!("career.awarded", self.name, tr!(key),
+                                 tr!(&format!("{}.for", key))));
+                match self.medals.iter_mut().find(|m| m.0 == medal) {
+                    Some(m) => m.1 += 1,
+                    None => self.medals.push((medal.to_string(), 1)),
+                }
+            }
+        }
+        if self.rank_index() > rank {
+            outln!("{}", tr!("career.promoted", self.name, self.rank()));
+        } else if self.rank_index() < rank {
+            outln!("{}", tr!("career.demoted", self.name, self.rank()));
+        }
+    }
+
+    /// The service record.
+    pub fn show(&self) {
+        outln!("{} {}", self.rank(), self.name);
+        outln!("{}", tr!("career.totals", self.games, self.wins, self.ships, self.score,
+                         self.best));
+        if let Some(&(name, wins, score)) = RANKS.get(self.rank_index() + 1) {
+            outln!("{}", tr!("career.next_rank", tr!(name), wins, score));
+        }
+        for (medal, count) in &self.medals {
+            let count = if *count > 1 { format!(" x{}", count) } else { String::new() };
+            outln!("    {}{}", medal_name(medal), count);
+        }
+        if !self.actions.is_empty() {
+            outln!("{}", tr!("career.recent"));
+            for &(won, turns, ships, score) in self.actions.iter().rev().take(5) {
+                let result = if won { tr!("career.victory") } else { tr!("career.defeat") };
+                outln!("{}", tr!("career.action", format!("{:7}", result), format!("{:3}", turns),
+                                 format!("{:3}", ships), format!("{:6}", score)));
+            }
+        }
+    }
+}
+
+/// Where careers are kept unless told otherwise.
+pub fn default_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".seawar_career"))
+}
+
+/// Every captain's career.
+pub struct Service {
+    careers: Vec<Career>,
+}
+
+impl Service {
+    /// The careers in the file at path.  A missing file has none, and lines
+    /// that can't be read are skipped.
+    pub fn load(path: &PathBuf) -> Service {
+        let text = fs::read_to_string(path).unwrap_or_default();
+        let mut careers: Vec<Career> = Vec::new();
+        for line in text.lines() {
+            let fields: Vec<_> = line.split('\t').collect();
+            match (fields[0], careers.last_mut()) {
+                ("captain", _) if fields.len() == 2 => careers.push(Career {
+                    name: fields[1].to_string(),
+                    ..Career::default()
+                }),
+                ("totals", Some(c)) if fields.len() == 6 => {
+                    c.games = fields[1].parse().unwrap_or(0);
+                    c.wins = fields[2].parse().unwrap_or(0);
+                    c.score = fields[3].parse().unwrap_or(0);
+                    c.best = fields[4].parse().unwrap_or(0);
+                    c.ships = fields[5].parse().unwrap_or(0);
+                }
+                ("medal", Some(c)) if fields.len() == 3 => {
+                    c.medals.push((fields[1].to_string(), fields[2].parse().unwrap_or(1)));
+                }
+                ("game", Some(c)) if fields.len() == 5 => {
+                    c.actions.push((fields[1] == "won", fields[2].parse().unwrap_or(0),
+                                    fields[3].parse().unwrap_or(0),
+                                    fields[4].parse().unwrap_or(0)));
+                }
+                _ => (),
+            }
+        }
+        Service { careers }
+    }
+
+    pub fn save(&self, path: &PathBuf) -> io::Result<()> {
+        let mut file = fs::File::create(path)?;
+        for c in &self.careers {
+            writeln!(file, "captain\t{}", c.name)?;
+            writeln!(file, "totals\t{}\t{}\t{}\t{}\t{}", c.games, c.wins, c.score, c.best, c.ships)?;
+            for (medal, count) in &c.medals {
+                writeln!(file, "medal\t{}\t{}", medal, count)?;
+            }
+            for &(won, turns, ships, score) in &c.actions {
+                writeln!(file, "game\t{}\t{}\t{}\t{}",
+                         if won { "won" } else { "lost" }, turns, ships, score)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The career of the captain called name, who may be new to the service.
+    pub fn career(&mut self, name: &str) -> &mut Career {
+        let name = name.replace(['\t', '\n'], " ");
+        match self.careers.iter().position(|c| c.name == name) {
+            Some(i) => &mut self.careers[i],
+            None => {
+                self.careers.push(Career { name, ..Career::default() });
+                self.careers.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testing::TempFile;
+
+    #[test]
+    fn ranks() {
+        let career = |wins, score| Career { wins, score, ..Career::default() };
+        assert_eq!(career(0, 0).rank_index(), 1);
+        assert_eq!(career(0, -1).rank_index(), 0);
+        // Promotion takes both the victories and the points.
+        assert_eq!(career(0, 5000).rank_index(), 1);
+        assert_eq!(career(1, 2000).rank_index(), 2);
+        assert_eq!(career(4, 7000).rank_index(), 3);
+        assert_eq!(career(100, 1000000).rank_index(), RANKS.len() - 1);
+        assert_eq!(career(0, 0).issue(), Supplies::default());
+        assert_eq!(career(10, 25000).issue().power, 1000);
+    }
+
+    #[test]
+    fn careers_file_round_trip() {
+        let file = TempFile::new("round-trip.career");
+        let mut service = Service::load(file.path());
+        assert!(service.careers.is_empty());
+        {
+            let nemo = service.career("Nemo");
+            nemo.games = 3;
+            nemo.wins = 1;
+            nemo.score = 2500;
+            nemo.best = 1800;
+            nemo.ships = 40;
+            nemo.medals.push(("Navy Cross".to_string(), 1));
+            nemo.medals.push(("Saboteur's Star".to_string(), 2));
+            nemo.actions.push((false, 12, 3, -150));
+            nemo.actions.push((true, 40, 25, 1800));
+        }
+        service.career("Ahab").games = 1;
+        assert_eq!(service.career("Nemo").games, 3);
+        service.save(file.path()).unwrap();
+        let mut loaded = Service::load(file.path());
+        assert_eq!(loaded.careers.len(), 2);
+        let nemo = loaded.career("Nemo");
+        assert_eq!((nemo.games, nemo.wins, nemo.score, nemo.best, nemo.ships),
+                   (3, 1, 2500, 1800, 40));
+        assert_eq!(nemo.medals, service.career("Nemo").medals);
+        assert_eq!(nemo.actions, service.career("Nemo").actions);
+    }
+
+    #[test]
+    fn unreadable_lines_are_skipped() {
+        let file = TempFile::with("damaged.career", "totals\t1\t1\t1\t1\t1\n\
+                          captain\tNemo\n\
+                          totals\t5\tmany\t900\t400\t12\n\
+                          medal\tPurple Heart\n\
+                          medal\tSilver Star\t2\n\
+                          game\twon\t30\n\
+                          game\tlost\t8\t0\t-250\n\
+                          promoted\tAdmiral\n\
+                          captain\n");
+        let service = Service::load(file.path());
+        assert_eq!(service.careers.len(), 1);
+        let nemo = &service.careers[0];
+        assert_eq!(nemo.name, "Nemo");
+        assert_eq!((nemo.games, nemo.wins, nemo.score), (5, 0, 900));
+        assert_eq!(nemo.medals, [("Silver Star".to_string(), 2)]);
+        assert_eq!(nemo.actions, [(false, 8, 0, -250)]);
+    }
+}
@@ -0,0 +1,103 @@
+// Where game text goes, and where answers to prompts come from.
+//
+// A local game has a single console, the terminal, shared by every player.
+// A networked game has one console per player, so each captain only sees
+// their own reports.  Output goes to the console of the player in focus, or
+// to everybody when nobody is in focus.
+
+use std::cell::{Cell, RefCell};
+use std::io::{stdin, stdout, Write};
+
+pub trait Console {
+    fn write(&mut self, text: &str);
+    // None once the other end has gone away.
+    fn read_line(&mut self) -> Option<String>;
+}
+
+pub struct Terminal;
+
+impl Console for Terminal {
+    fn write(&mut self, text: &str) {
+        print!("{}", text);
+        stdout().flush().unwrap();
+    }
+
+    fn read_line(&mut self) -> Option<String> {
+        let mut result = String::new();
+        match stdin().read_line(&mut result) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(result),
+        }
+    }
+}
+
+// Nobody watching: output is thrown away and there are no answers.  For
+// games played entirely by the computer.
+pub struct Silent;
+
+impl Console for Silent {
+    fn write(&mut self, _text: &str) {}
+
+    fn read_line(&mut self) -> Option<String> {
+        None
+    }
+}
+
+thread_local! {
+    static CONSOLES: RefCell<Vec<Box<dyn Console>>> = RefCell::new(vec![Box::new(Terminal)]);
+    static FOCUS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+// Replace the default terminal with one console per player.
+pub fn install(consoles: Vec<Box<dyn Console>>) {
+    CONSOLES.with(|c| *c.borrow_mut() = consoles);
+}
+
+// Direct output to player id, or to everyone.  Players without a console
+// of their own share the first one.
+pub fn focus(id: Option<usize>) {
+    FOCUS.with(|f| f.set(id));
+}
+
+fn focused(count: usize) -> usize {
+    match FOCUS.with(|f| f.get()) {
+        Some(id) if id < count => id,
+        _ => 0,
+    }
+}
+
+pub fn write(text: &str) {
+    CONSOLES.with(|c| {
+        let mut consoles = c.borrow_mut();
+        if FOCUS.with(|f| f.get()).is_none() {
+            for console in consoles.iter_mut() {
+                console.write(text);
+            }
+        } else {
+            let i = focused(consoles.len());
+            consoles[i].write(text);
+        }
+    })
+}
+
+pub fn read_line() -> Option<String> {
+    CONSOLES.with(|c| {
+        let mut consoles = c.borrow_mut();
+        let i = focused(consoles.len());
+        consoles[i].read_line()
+    })
+}
+
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => ($crate::console::write(&format!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! outln {
+    () => ($crate::console::write("\n"));
+    ($($arg:tt)*) => ({
+        $crate::console::write(&format!($($arg)*));
+        $crate::console::write("\n");
+    });
+}
@@ -0,0 +1,123 @@
+// The after-action report.  Each sub keeps a service record of what it
+// sank and with what, what it used up and what it suffered, which is read
+// out once the game is over.
+
+use enum_map::EnumMap;
+
+use mission::{Failure, Verdict};
+use super::{Cause, Outcome, SubSystem};
+
+#[derive(Clone, Copy, Debug, EnumMap)]
+pub enum Weapon {
+    Torpedo,
+    Missile,
+    Sabotage,
+    Ramming,
+}
+
+/// What one sub did and went through in a game.
+#[derive(Clone, Debug, Default)]
+pub struct Record {
+    pub sunk: EnumMap<Weapon, u32>,
+    pub torpedos_fired: u32,
+    pub missiles_fired: u32,
+    // Burned by missiles or turned into power.
+    pub fuel_used: u32,
+    pub damage: EnumMap<SubSystem, f32>,
+    // Lost on missions.  Those who go down with the sub aren't counted here.
+    pub crew_lost: u32,
+    pub resupplies: u32,
+    pub hq_destroyed: bool,
+}
+
+/// Enemy ships lost to the sea rather than to a sub.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Wrecks {
+    pub mines: u32,
+    pub monsters: u32,
+}
+
+/// How a sub was lost.
+pub fn cause_text(cause: Cause) -> String {
+    tr!(match cause {
+        Cause::Mine => "cause.mine",
+        Cause::Monster => "cause.monster",
+        Cause::Rammed => "cause.rammed",
+        Cause::Supercritical => "cause.supercritical",
+        Cause::PowerExhausted => "cause.power_exhausted",
+        Cause::Retaliation => "cause.retaliation",
+        Cause::Crushed => "cause.crushed",
+        Cause::Misfire => "cause.misfire",
+        Cause::FriendlyFire => "cause.friendly_fire",
+        Cause::Surrendered => "cause.surrendered",
+    })
+}
+
+fn weapon_name(weapon: Weapon) -> String {
+    tr!(match weapon {
+        Weapon::Torpedo => "weapon.torpedo",
+        Weapon::Missile => "weapon.missile",
+        Weapon::Sabotage => "weapon.sabotage",
+        Weapon::Ramming => "weapon.ramming",
+    })
+}
+
+// One line of a sub's record, the figures lined up.
+fn item(key: &str, value: &dyn std::fmt::Display) {
+    outln!("    {:21}{}", tr!(key), value);
+}
+
+/// Read out how the game went, sub by sub.
+pub fn after_action(outcome: &Outcome) {
+    outln!();
+    outln!("{}", tr!("debrief.title"));
+    let ending = tr!(match outcome.verdict {
+        _ if outcome.ships_left == 0 => "ending.all_sunk",
+        Some(Verdict::Accomplished) => "ending.accomplished",
+        Some(Verdict::Failed(Failure::TurnLimit(_))) => "ending.out_of_time",
+        Some(Verdict::Failed(Failure::HqLost)) => "ending.hq_lost",
+        Some(Verdict::Failed(Failure::Detected)) => "ending.detected",
+        None if outcome.players.iter().all(|pi| !pi.alive) => "ending.all_lost",
+        None => "ending.called_off",
+    });
+    outln!("{}", tr!("debrief.lasted", outcome.turns, ending));
+    if outcome.wrecks.mines + outcome.wrecks.monsters > 0 {
+        outln!("{}", tr!("debrief.wrecks", outcome.wrecks.mines + outcome.wrecks.monsters,
+                         outcome.wrecks.mines, outcome.wrecks.monsters));
+    }
+    for pi in &outcome.players {
+        let record = &pi.record;
+        outln!();
+        outln!("{}", pi.name);
+        match pi.cause {
+            None => outln!("{}", tr!("debrief.survived", outcome.turns)),
+            Some(cause) => outln!("{}", tr!("debrief.lost", cause_text(cause))),
+        }
+        let sunk: Vec<_> = record.sunk.iter()
+            .filter(|&(_, &n)| n > 0)
+            .map(|(weapon, n)| tr!("debrief.sunk_by", n, weapon_name(weapon)))
+            .collect();
+        if sunk.is_empty() {
+            item("debrief.ships_sunk", &tr!("none"));
+        } else {
+            item("debrief.ships_sunk", &format!("{} ({})", pi.ships_sunk, sunk.join(", ")));
+        }
+        item("debrief.torpedos", &record.torpedos_fired);
+        item("debrief.missiles", &record.missiles_fired);
+        item("debrief.fuel", &tr!("lbs", record.fuel_used));
+        let lost = record.crew_lost + if pi.alive { 0 } else { pi.crew };
+        item("debrief.crew_lost", &lost);
+        item("debrief.resupplies", &record.resupplies);
+        let damaged: Vec<_> = record.damage.iter()
+            .filter(|&(_, &d)| d > 0.)
+            .collect();
+        if damaged.is_empty() {
+            item("debrief.damage", &tr!("none"));
+        } else {
+            outln!("    {}", tr!("debrief.damage"));
+            for (system, damage) in damaged {
+                outln!("        {:12} {:.1}", system.name(), damage);
+            }
+        }
+    }
+}
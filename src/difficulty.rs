@@ -0,0 +1,201 @@
+// How hard a game is.  Everything that sets the odds lives in one profile:
+// how many enemies there are, how hard depth charges hit, how quickly
+// repairs get done and how often headquarters can resupply.  Commander is
+// the game as it always was.
+
+use super::scaled;
+
+/// A difficulty profile.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difficulty {
+    pub name: &'static str,
+    // Enemy ships and mines, fewest and most, and sea monsters, all for a
+    // 20x20 ocean and scaled up for larger ones.
+    pub ships: (usize, usize),
+    pub mines: (usize, usize),
+    pub monsters: usize,
+    // Most threat from depth charges that doesn't do damage to speak of,
+    // and that does light, moderate and heavy damage.  Higher is kinder.
+    pub threat: [f32; 4],
+    // Repair speed, 1 is normal.
+    pub repair: f32,
+    // Times headquarters can resupply each sub.
+    pub resupplies: u32,
+    // What the score is multiplied by.
+    pub reward: f32,
+}
+
+const STANDARD_THREAT: [f32; 4] = [0.13, 0.36, 0.6, 0.9];
+
+/// The presets, easiest first.
+pub const PRESETS: [Difficulty; 5] = [
+    Difficulty {
+        name: "cadet", ships: (8, 15), mines: (4, 8), monsters: 1,
+        threat: [0.26, 0.72, 1.2, 1.8], repair: 2., resupplies: 4, reward: 0.5,
+    },
+    Difficulty {
+        name: "lieutenant", ships: (12, 22), mines: (6, 11), monsters: 2,
+        threat: [0.195, 0.54, 0.9, 1.35], repair: 1.5, resupplies: 3, reward: 0.75,
+    },
+    Difficulty {
+        name: "commander", ships: (15, 30), mines: (8, 14), monsters: 4,
+        threat: STANDARD_THREAT, repair: 1., resupplies: 2, reward: 1.,
+    },
+    Difficulty {
+        name: "captain", ships: (20, 35), mines: (10, 18), monsters: 5,
+        threat: [0.104, 0.288, 0.48, 0.72], repair: 0.8, resupplies: 2, reward: 1.5,
+    },
+    Difficulty {
+        name: "admiral", ships: (25, 40), mines: (12, 22), monsters: 6,
+        threat: [0.078, 0.216, 0.36, 0.54], repair: 0.6, resupplies: 1, reward: 2.,
+    },
+];
+
+impl Difficulty {
+    pub fn preset(name: &str) -> Option<Difficulty> {
+        PRESETS.iter().find(|d| d.name == name).cloned()
+    }
+
+    /// Read a difficulty: a preset, optionally followed by changes, as in
+    /// "captain,repair=1.5,monsters=2".  "custom" on its own is commander.
+    /// Anything changed makes it custom.
+    pub fn parse(text: &str) -> Result<Difficulty, String> {
+        let text = text.to_lowercase();
+        let mut parts = text.split(',');
+        let base = parts.next().unwrap_or("");
+        let mut difficulty = match base {
+            "custom" => Difficulty { name: "custom", ..Difficulty::default() },
+            _ => Difficulty::preset(base).ok_or_else(|| {
+                let names: Vec<_> = PRESETS.iter().map(|d| d.name).collect();
+                tr!("difficulty.unknown", base, names.join(", "))
+            })?,
+        };
+        for part in parts {
+            let (key, value) = match part.split_once('=') {
+                Some(kv) => kv,
+                None => return Err(tr!("difficulty.expected", part)),
+            };
+            difficulty.change(key.trim(), value.trim())?;
+            difficulty.name = "custom";
+        }
+        Ok(difficulty)
+    }
+
+    fn change(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let range = |value: &str| -> Result<(usize, usize), String> {
+            let (lo, hi) = value.split_once('-').unwrap_or((value, value));
+            match (lo.parse(), hi.parse()) {
+                (Ok(lo), Ok(hi)) if lo <= hi => Ok((lo, hi)),
+                _ => Err(tr!("difficulty.range", key, value)),
+            }
+        };
+        let count = |value: &str| -> Result<usize, String> {
+            value.parse().map_err(|_| tr!("difficulty.count", key, value))
+        };
+        let number = |value: &str| -> Result<f32, String> {
+            match value.parse::<f32>() {
+                Ok(n) if n > 0. && n.is_finite() => Ok(n),
+                _ => Err(tr!("difficulty.number", key, value)),
+            }
+        };
+        match key {
+            "ships" => {
+                self.ships = range(value)?;
+                if self.ships.0 == 0 {
+                    return Err(tr!("difficulty.no_ships"));
+                }
+            }
+            "mines" => self.mines = range(value)?,
+            "monsters" => self.monsters = count(value)?,
+            "threat" => {
+                let shielding = number(value)?;
+                for (t, s) in self.threat.iter_mut().zip(STANDARD_THREAT.iter()) {
+                    *t = s * shielding;
+                }
+            }
+            "repair" => self.repair = number(value)?,
+            "resupplies" => self.resupplies = count(value)? as u32,
+            "reward" => self.reward = number(value)?,
+            _ => return Err(tr!("difficulty.key", key)),
+        }
+        Ok(())
+    }
+
+    /// What the profile comes to, with as many enemies as the ocean in
+    /// play holds.
+    pub fn describe(&self) -> String {
+        tr!("difficulty.describe", self.name, scaled(self.ships.0), scaled(self.ships.1),
+            scaled(self.mines.0), scaled(self.mines.1), scaled(self.monsters),
+            format!("{:.2}", self.threat[0] / STANDARD_THREAT[0]),
+            format!("{:.2}", self.repair), self.resupplies, format!("{:.2}", self.reward))
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Difficulty {
+        Difficulty::preset("commander").unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets() {
+        for preset in PRESETS.iter() {
+            assert_eq!(&Difficulty::parse(preset.name).unwrap(), preset);
+        }
+        assert_eq!(Difficulty::parse("Admiral").unwrap().name, "admiral");
+        assert_eq!(Difficulty::default().threat, STANDARD_THREAT);
+        let custom = Difficulty::parse("custom").unwrap();
+        assert_eq!(custom.name, "custom");
+        assert_eq!(Difficulty { name: "commander", ..custom }, Difficulty::default());
+        // Harder presets have more enemies, hit harder and pay better.
+        for pair in PRESETS.windows(2) {
+            assert!(pair[0].ships.1 < pair[1].ships.1);
+            assert!(pair[0].threat[0] > pair[1].threat[0]);
+            assert!(pair[0].reward < pair[1].reward);
+        }
+    }
+
+    #[test]
+    fn changes() {
+        let d = Difficulty::parse("captain,repair=2").unwrap();
+        assert_eq!(d.name, "custom");
+        assert_eq!(d.repair, 2.);
+        assert_eq!(d.ships, PRESETS[3].ships);
+
+        let d = Difficulty::parse("cadet, ships=10-12 ,mines=3,monsters=0,resupplies=9").unwrap();
+        assert_eq!(d.ships, (10, 12));
+        assert_eq!(d.mines, (3, 3));
+        assert_eq!(d.monsters, 0);
+        assert_eq!(d.resupplies, 9);
+        assert_eq!(d.reward, PRESETS[0].reward);
+
+        let d = Difficulty::parse("custom,threat=2,reward=0.5").unwrap();
+        assert_eq!(d.threat[3], STANDARD_THREAT[3] * 2.);
+        assert_eq!(d.reward, 0.5);
+    }
+
+    #[test]
+    fn bad_difficulties() {
+        assert!(Difficulty::parse("").is_err());
+        assert!(Difficulty::parse("vice-admiral").is_err());
+        assert!(Difficulty::parse("captain,repair").is_err());
+        assert!(Difficulty::parse("captain,speed=2").is_err());
+        assert!(Difficulty::parse("captain,ships=0").is_err());
+        assert!(Difficulty::parse("captain,ships=0-5").is_err());
+        assert!(Difficulty::parse("captain,ships=20-10").is_err());
+        assert!(Difficulty::parse("captain,mines=-3").is_err());
+        assert!(Difficulty::parse("captain,mines=lots").is_err());
+        assert_eq!(Difficulty::parse("captain,monsters=2-5").unwrap_err(),
+                   "monsters wants a whole number, like 2, not '2-5'");
+        assert!(Difficulty::parse("captain,resupplies=1-3").is_err());
+        assert!(Difficulty::parse("captain,resupplies=-1").is_err());
+        assert!(Difficulty::parse("captain,repair=0").is_err());
+        assert!(Difficulty::parse("captain,repair=-1").is_err());
+        assert!(Difficulty::parse("captain,threat=inf").is_err());
+        assert!(Difficulty::parse("captain,reward=NaN").is_err());
+    }
+}
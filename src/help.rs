@@ -0,0 +1,139 @@
+// The ship's manual, for the help command.  Figures come from the rules
+// in play.
+
+use enum_map::EnumMap;
+
+use super::{parse_command, print_directions, Command, SubSystem};
+use rules::rules;
+use view::draw_legend;
+
+// Pages that aren't about a single command.
+const TOPICS: [(&str, &str); 7] = [
+    ("directions", "topic.directions"),
+    ("chart", "topic.chart"),
+    ("enemies", "topic.enemies"),
+    ("damage", "topic.damage"),
+    ("depth", "topic.depth"),
+    ("orders", "topic.orders"),
+    ("missions", "topic.missions"),
+];
+
+fn percent(chance: f32) -> u32 {
+    (chance * 100.).round() as u32
+}
+
+fn index() {
+    outln!("{}", tr!("help.index"));
+    for (topic, about) in TOPICS.iter() {
+        outln!("    {:12} {}", topic, tr!(about));
+    }
+}
+
+fn command(command: Command) {
+    use Command::*;
+    let rules = rules();
+    match command {
+        Navigate => {
+            outln!("{}", tr!("help.navigate", rules.power_per_move, rules.navigate_crew,
+                             rules.shallow_depth, rules.safe_power,
+                             100 - percent(rules.pile_safety)));
+        }
+        Sonar => {
+            outln!("{}", tr!("help.sonar", rules.sonar_power, rules.sonar_crew));
+        }
+        Torpedo => {
+            outln!("{}", tr!("help.torpedo", rules.torpedo_power, rules.torpedo_crew,
+                             rules.torpedo_range - rules.torpedo_spread, rules.torpedo_range,
+                             rules.deep_torpedo_bonus, rules.shallow_depth, rules.deep_depth));
+        }
+        Missile => {
+            outln!("{}", tr!("help.missile", rules.fuel_per_cell, rules.missile_crew,
+                             rules.shallow_depth, rules.deep_depth));
+        }
+        Manuever => {
+            outln!("{}", tr!("help.manuever", rules.feet_per_power, rules.manuever_crew,
+                             rules.crush_depth));
+        }
+        Status => {
+            outln!("{}", tr!("help.status", rules.status_crew));
+        }
+        Resupply => {
+            let stocks = if rules.classic { tr!("help.sets_stocks") } else { tr!("help.tops_up") };
+            outln!("{}", tr!("help.resupply", rules.docking_range, rules.shallow_depth, stocks,
+                             rules.resupply_power, rules.resupply_torpedos,
+                             rules.resupply_missiles, rules.resupply_fuel, rules.resupply_crew));
+        }
+        Sabotage => {
+            outln!("{}", tr!("help.sabotage", rules.sabotage_range, rules.sabotage_crew,
+                             rules.skeleton_crew, percent(rules.saboteur_survival)));
+        }
+        Convert => {
+            outln!("{}", tr!("help.convert", rules.fuel_per_power, rules.fuel_per_power,
+                             rules.convert_crew));
+        }
+        Surrender => {
+            outln!("{}", tr!("help.surrender"));
+        }
+        Undo => {
+            outln!("{}", tr!("help.undo"));
+        }
+        Log => {
+            outln!("{}", tr!("help.log"));
+        }
+    }
+}
+
+fn topic(topic: &str) {
+    let rules = rules();
+    match topic {
+        "directions" => {
+            print_directions();
+            outln!("{}", tr!("help.north"));
+        }
+        "chart" => {
+            outln!("{}", tr!("help.sonar_shows"));
+            draw_legend(0);
+            outln!("{}", tr!("help.chart"));
+        }
+        "enemies" => {
+            outln!("{}", tr!("help.enemies", rules.ship_range, rules.monster_reach,
+                             percent(rules.monster_bite)));
+        }
+        "damage" => {
+            outln!("{}", tr!("help.damage"));
+            let systems: Vec<_> = EnumMap::<SubSystem, f32>::new().iter()
+                .map(|(s, _)| s.name())
+                .collect();
+            outln!("    {}", systems.join(", "));
+            outln!("{}", tr!("help.repairs", rules.shallow_depth, rules.deep_depth));
+        }
+        "depth" => {
+            outln!("{}", tr!("help.depth", rules.shallow_depth, rules.shallow_depth,
+                             rules.deep_torpedo_bonus, rules.deep_depth, rules.crush_depth));
+        }
+        "orders" => {
+            outln!("{}", tr!("help.orders"));
+        }
+        "missions" => {
+            outln!("{}", tr!("help.missions", rules.ship_range, rules.shallow_depth));
+        }
+        _ => index(),
+    }
+}
+
+/// Show the page for a command or topic, or the index.
+pub fn show(about: &str) {
+    let about = about.trim().to_lowercase();
+    let topics: Vec<_> = TOPICS.iter().filter(|t| t.0.starts_with(&about)).collect();
+    if about.is_empty() {
+        index();
+    } else if let Some(t) = topics.iter().find(|t| t.0 == about) {
+        topic(t.0);
+    } else if let Ok(c) = parse_command(&about) {
+        command(c);
+    } else if topics.len() == 1 {
+        topic(topics[0].0);
+    } else {
+        index();
+    }
+}
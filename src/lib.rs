@@ -0,0 +1,2385 @@
+extern crate rand;
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+// Magic to generate random SubSystem.
+#[macro_use]
+extern crate rand_derive;
+
+extern crate enum_map;
+use enum_map::EnumMap;
+
+use std::cell::{Cell, RefCell};
+use std::{process, thread, time};
+
+extern crate enum_map_derive;
+
+#[macro_use]
+pub mod console;
+#[macro_use]
+pub mod text;
+pub mod net;
+pub mod captain;
+use captain::{Captain, Observation};
+pub mod view;
+pub mod bot;
+mod help;
+pub mod logbook;
+use logbook::{Event, Logbook};
+pub mod debrief;
+use debrief::{Record, Weapon, Wrecks};
+pub mod score;
+pub mod career;
+pub mod difficulty;
+pub mod rules;
+use rules::rules;
+pub mod mission;
+use mission::{Failure, Goal, Mission, Situation, Verdict};
+use difficulty::Difficulty;
+#[cfg(test)]
+mod testing;
+use view::{Chart, Viewport};
+
+// Dimensions of the playing area.  These are fixed for the duration of a
+// game, so they live in a thread-local rather than being passed everywhere.
+#[derive(Clone, Copy, Debug)]
+pub struct Ocean {
+    pub width: usize,
+    pub height: usize,
+    pub wrap: bool,
+}
+
+pub const MIN_SIZE: usize = 10;
+pub const MAX_SIZE: usize = 200;
+
+thread_local!(static OCEAN: Cell<Ocean> = const { Cell::new(Ocean { width: 20, height: 20, wrap: false }) });
+
+pub fn ocean() -> Ocean {
+    OCEAN.with(|o| o.get())
+}
+
+pub fn set_ocean(o: Ocean) {
+    OCEAN.with(|cell| cell.set(o));
+}
+
+// WIDTHxHEIGHT from the command line, keeping the current wrap setting.
+pub fn parse_size(arg: Option<String>) -> Option<Ocean> {
+    let arg = arg?;
+    let mut dims = arg.splitn(2, ['x', 'X']);
+    let width = dims.next()?.parse::<usize>().ok()?;
+    let height = dims.next()?.parse::<usize>().ok()?;
+    let valid = MIN_SIZE..=MAX_SIZE;
+    if valid.contains(&width) && valid.contains(&height) {
+        Some(Ocean { width, height, wrap: ocean().wrap })
+    } else {
+        None
+    }
+}
+
+impl Ocean {
+    // Signed offset from a to b along an axis of length len, the short way
+    // round if the ocean wraps.
+    fn delta(&self, a: usize, b: usize, len: usize) -> i32 {
+        let d = b as i32 - a as i32;
+        let len = len as i32;
+        if self.wrap && d.abs() * 2 > len {
+            d - d.signum() * len
+        } else {
+            d
+        }
+    }
+
+    // Travel n cells from (x, y) in direction (dx, dy).  Returns None if
+    // that would leave the area, unless the ocean wraps at the edges.
+    fn offset(&self, x: usize, y: usize, dx: i8, dy: i8, n: i32) -> Option<(usize, usize)> {
+        let (w, h) = (self.width as i64, self.height as i64);
+        let nx = x as i64 + dx as i64 * n as i64;
+        let ny = y as i64 + dy as i64 * n as i64;
+        if self.wrap {
+            Some((nx.rem_euclid(w) as usize, ny.rem_euclid(h) as usize))
+        } else if (0..w).contains(&nx) && (0..h).contains(&ny) {
+            Some((nx as usize, ny as usize))
+        } else {
+            None
+        }
+    }
+
+    fn step(&self, x: usize, y: usize, dx: i8, dy: i8) -> Option<(usize, usize)> {
+        self.offset(x, y, dx, dy, 1)
+    }
+
+    // Separation along one axis of length len, the short way round if the
+    // ocean wraps.
+    fn separation(&self, a: usize, b: usize, len: usize) -> f32 {
+        let d = (a as f32 - b as f32).abs();
+        if self.wrap {
+            d.min(len as f32 - d)
+        } else {
+            d
+        }
+    }
+
+    // Relative to the original 20x20 board, for scaling entity counts.
+    fn scale(&self) -> f32 {
+        (self.width * self.height) as f32 / 400.
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    Navigate,
+    Sonar,
+    Torpedo,
+    Missile,
+    Manuever,
+    Status,
+    Resupply,
+    Sabotage,
+    Convert,
+    Surrender,
+    Undo,
+    Log,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Conversion {
+    FuelToPower,
+    PowerToFuel,
+}
+
+// A command, along with the details it needs.  Anything left as None is
+// asked for when the command is carried out.
+#[derive(Clone, Debug)]
+pub struct Order {
+    pub command: Command,
+    pub direction: Option<(i8, i8)>,
+    // Power to navigate with, fuel for a missile, men to send on a
+    // sabotage mission, or power or fuel to convert.
+    pub amount: Option<u32>,
+    pub depth: Option<i32>,
+    pub conversion: Option<Conversion>,
+    // Go ahead with a missile launch from an unsafe depth
+    pub confirm: Option<bool>,
+    // Given at a console, so follow-up questions like panning the sonar
+    // view can be asked.
+    pub interactive: bool,
+}
+
+impl Order {
+    pub fn new(command: Command) -> Order {
+        Order {
+            command,
+            direction: None,
+            amount: None,
+            depth: None,
+            conversion: None,
+            confirm: None,
+            interactive: false,
+        }
+    }
+
+    pub fn with_direction(mut self, direction: (i8, i8)) -> Order {
+        self.direction = Some(direction);
+        self
+    }
+
+    pub fn with_amount(mut self, amount: u32) -> Order {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn with_depth(mut self, depth: i32) -> Order {
+        self.depth = Some(depth);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, EnumMap, Rand)]
+pub enum SubSystem {
+    Engines,
+    Sonar,
+    Torpedos,
+    Missiles,
+    Manuevering,
+    Computers,
+    Resupply,
+    Sabotage,
+    Converter,
+}
+
+impl SubSystem {
+    /// What the system is called, in the language in play.
+    pub fn name(self) -> String {
+        use SubSystem::*;
+        tr!(match self {
+            Engines => "system.engines",
+            Sonar => "system.sonar",
+            Torpedos => "system.torpedos",
+            Missiles => "system.missiles",
+            Manuevering => "system.manuevering",
+            Computers => "system.computers",
+            Resupply => "system.resupply",
+            Sabotage => "system.sabotage",
+            Converter => "system.converter",
+        })
+    }
+}
+
+// What finished off a sub.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cause {
+    Mine,
+    Monster,
+    Rammed,
+    Supercritical,
+    PowerExhausted,
+    // Depth charges took the last of the power, and none was made in time.
+    Retaliation,
+    Crushed,
+    Misfire,
+    FriendlyFire,
+    Surrendered,
+}
+
+// Stocks at the end of a turn.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Supplies {
+    pub power: u32,
+    pub fuel: u32,
+    pub torpedos: u32,
+    pub missiles: u32,
+    pub crew: u32,
+}
+
+#[derive(Clone)]
+pub struct PlayerInfo {
+    pub id: usize,
+    pub name: String,
+    pub alive: bool,
+    pub cause: Option<Cause>,
+    pub damage: EnumMap<SubSystem, f32>,
+    pub depth: i32,
+    pub crew: u32,
+    pub power: u32,
+    pub fuel: u32,
+    pub torpedos: u32,
+    pub missiles: u32,
+    pub resupply_left: u32,
+    pub last_sonar: Option<Chart>,
+    pub ships_sunk: u32,
+    pub history: Vec<Supplies>,
+    // Depth charges came down since the sub's last turn.
+    pub under_fire: bool,
+    pub log: Logbook,
+    pub record: Record,
+}
+
+fn prompt(pstr: &str) -> String {
+    out!("{}? ", pstr);
+    let mut result = console::read_line().unwrap_or_else(|| {
+        // Out of input, nothing more can be done.
+        outln!();
+        process::exit(0);
+    });
+    let len = result.trim_end().len();
+    result.truncate(len);
+    result
+}
+
+// Prompt for the details of an order.  An empty answer, or q, calls the
+// order off.
+fn ask(pstr: &str) -> Option<String> {
+    let input = prompt(pstr);
+    if input.is_empty() || input.eq_ignore_ascii_case("q") {
+        None
+    } else {
+        Some(input)
+    }
+}
+
+// Back to the orders prompt, with nothing spent.
+fn belay(pi: &PlayerInfo) -> bool {
+    outln!("{}", tr!("belay", pi.name));
+    false
+}
+
+impl PlayerInfo {
+    fn new(id: usize, name: String) -> PlayerInfo {
+        PlayerInfo {
+            id,
+            name,
+            alive: true,
+            cause: None,
+            damage: EnumMap::<SubSystem, f32>::new(),
+            depth: 100,
+            crew: 30,
+            power: 6000,
+            fuel: 2500,
+            torpedos: 10,
+            missiles: 3,
+            resupply_left: 2,
+            last_sonar: None,
+            ships_sunk: 0,
+            history: Vec::new(),
+            under_fire: false,
+            log: Logbook::default(),
+            record: Record::default(),
+        }
+    }
+
+    fn die(&mut self, cause: Cause) {
+        self.alive = false;
+        self.cause = Some(cause);
+        self.log.note(Event::Lost(cause));
+    }
+
+    fn credit(&mut self, weapon: Weapon, ships: u32) {
+        self.ships_sunk += ships;
+        self.record.sunk[weapon] += ships;
+    }
+
+    fn supplies(&self) -> Supplies {
+        Supplies {
+            power: self.power,
+            fuel: self.fuel,
+            torpedos: self.torpedos,
+            missiles: self.missiles,
+            crew: self.crew,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Position {
+    fn range(&self, other: &Position) -> (f32, f32) {
+        let o = ocean();
+        (o.separation(self.x, other.x, o.width),
+         o.separation(self.y, other.y, o.height))
+    }
+    fn in_range(&self, other: &Position, range: f32) -> bool {
+        let (dx, dy) = self.range(other);
+        dx <= range && dy <= range
+    }
+
+    // Return the euclidian distance of the other position, but only if
+    // each dimension is within the distance specified by range.
+    fn distance_within(&self, other: &Position, range: f32) -> Option<f32> {
+        let (dx, dy) = self.range(other);
+        if dx <= range && dy <= range {
+            return Some((dx*dx + dy*dy).sqrt());
+        }
+        None
+    }
+
+    // Offset to the other position, in cells
+    fn heading(&self, other: &Position) -> (i32, i32) {
+        let o = ocean();
+        (o.delta(self.x, other.x, o.width), o.delta(self.y, other.y, o.height))
+    }
+
+    // Return the euclidian distance of the other position
+    fn distance(&self, other: &Position) -> f32 {
+        let (dx, dy) = self.range(other);
+        (dx*dx + dy*dy).sqrt()
+    }
+}
+
+// Each player's sub is tagged with their index into the list of players.
+#[derive(PartialEq, Clone, Debug)]
+pub enum EType {
+    Player(usize),
+    Island,
+    Ship,
+    Mine,
+    HQ,
+    Monster
+}
+
+// Collision outcomes / resolutions
+#[derive(Debug)]
+enum EResolution {
+    CrasheeDestroyed,
+    MoverDestroyed,
+    MoverChangeDirection
+}
+
+#[derive(Clone, Debug)]
+enum Component {
+    Velocity(i8, i8),
+    // The ship the mission is out to sink.
+    Flagship,
+}
+
+impl Component {
+    fn new_vel() -> Component {
+        let mut dx = 0;
+        let mut dy = 0;
+        while dx == 0 && dy == 0 {
+            dx = with_rng(|r| r.gen_range(-1, 2));
+            dy = with_rng(|r| r.gen_range(-1, 2));
+        }
+        Component::Velocity(dx, dy)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Entity {
+    pos: Position,
+    etype: EType,
+    components: Vec<Component>
+}
+
+impl Entity {
+    // syntactic sugar
+    fn new(x: usize, y: usize, etype: EType) -> Entity {
+        Entity {
+            pos: Position {x, y},
+            etype,
+            components: Vec::new(),
+        }
+    }
+}
+
+// Note:  If you alias like this, you can't define your own methods.
+type EntityColl = std::collections::VecDeque<Entity>;
+
+// Note:  If you newtype like this, you can't use existing methods by default.
+// struct EntityColl(std::collections::VecDeque<Entity>);
+
+fn count_all_of(entities: &EntityColl, etype: EType) -> u32 {
+    entities.iter().
+         filter(|e: &&Entity| e.etype == etype).
+         count() as u32
+}
+
+// All randomness comes from one generator, so a game can be replayed from
+// its seed.
+thread_local!(static RNG: RefCell<XorShiftRng> = RefCell::new(XorShiftRng::new_unseeded()));
+
+pub fn seed(seed: u64) {
+    // XorShift can't start from all zeros
+    let seed = [(seed >> 32) as u32, seed as u32, 0x9e37_79b9, 0x7f4a_7c15];
+    RNG.with(|r| *r.borrow_mut() = XorShiftRng::from_seed(seed));
+}
+
+fn with_rng<T, F: FnOnce(&mut XorShiftRng) -> T>(f: F) -> T {
+    RNG.with(|r| f(&mut r.borrow_mut()))
+}
+
+// Headless games skip the dramatic pauses.
+thread_local!(static FAST: Cell<bool> = const { Cell::new(false) });
+
+pub fn set_fast(fast: bool) {
+    FAST.with(|f| f.set(fast));
+}
+
+fn pause(millis: u64) {
+    if !FAST.with(|f| f.get()) && !accessible() {
+        thread::sleep(time::Duration::from_millis(millis));
+    }
+}
+
+// Screen readers get sentences in place of pictures, and no animation.
+thread_local!(static ACCESSIBLE: Cell<bool> = const { Cell::new(false) });
+
+pub fn set_accessible(accessible: bool) {
+    ACCESSIBLE.with(|a| a.set(accessible));
+}
+
+fn accessible() -> bool {
+    ACCESSIBLE.with(|a| a.get())
+}
+
+// Shorthand function, Original BASIC code uses this a lot.
+fn rnd() -> f32 {
+    with_rng(|r| r.next_f32())
+}
+
+fn change_direction(mut e: Entity) -> Entity {
+    // TODO:  If we have different types of components, replace the right one
+    e.components[0] = Component::new_vel();
+    // outln!("New velocity: {:?}", e.components[0]);
+    e
+}
+
+fn get_first_pos(entities: &EntityColl, etype: EType) -> Option<Position> {
+    for e in entities.iter() {
+        if etype == e.etype {
+            return Some(e.pos.clone());
+        }
+    }
+    None
+}
+
+fn get_first(entities: &mut EntityColl, etype: EType) -> Option<Entity> {
+    for i in 0..entities.len() {
+        if etype == entities[i].etype {
+            return entities.swap_remove_back(i);
+        }
+    }
+    None
+}
+
+fn get_collision(entities: &mut EntityColl, x:usize, y:usize) -> Option<Entity> {
+    let pos = Position{x, y};
+    for i in 0..entities.len() {
+        if pos == entities[i].pos {
+            return entities.swap_remove_back(i);
+        }
+    }
+    None
+}
+
+fn check_collision(entities: &EntityColl, x:usize, y:usize) -> bool {
+    for e in entities {
+        // Why doesn't this work? if Position{x, y} == e.pos {
+        let pos = Position{x, y};
+        if pos == e.pos {
+            return true;
+        }
+    }
+    false
+}
+
+fn place_random(entities: &EntityColl, etype: EType) -> Entity {
+    loop {
+        let x = with_rng(|r| r.gen_range(0, ocean().width));
+        let y = with_rng(|r| r.gen_range(0, ocean().height));
+        if !check_collision(entities, x, y) {
+            return Entity::new(x, y, etype);
+        }
+    }
+}
+
+// Number of things to place, scaled up for larger oceans.  At least one,
+// unless there are to be none.
+fn scaled(count: usize) -> usize {
+    ((count as f32 * ocean().scale()).round() as usize).max(count.min(1))
+}
+
+fn setup(players: usize, difficulty: &Difficulty) -> EntityColl {
+    let (ships, mines) = (difficulty.ships, difficulty.mines);
+    let mut entities = EntityColl::new();
+    let (cx, cy) = (ocean().width / 2, ocean().height / 2);
+
+    // Island Bitmap
+    let island = [
+        0, 1, 1, 1, 0, 0,
+        0, 1, 1, 1, 1, 0,
+        1, 1, 1, 0, 1, 1,
+        1, 1, 0, 0, 0, 1,
+        1, 1, 0, 0, 1, 1,
+        0, 1, 1, 0, 1, 0,
+        0, 0, 1, 0, 0, 0,
+    ];
+    let mut i = 0;
+    for y in cy-3..cy+4 {
+        for x in cx-3..cx+3 {
+            if island[i] == 1 {
+                entities.push_back(Entity::new(x, y, EType::Island));
+            }
+            i += 1;
+        }
+    }
+
+    // Players start in the lagoon
+    let lagoon = [(cx, cy), (cx-1, cy), (cx+1, cy), (cx, cy-1)];
+    for (id, &(x, y)) in lagoon.iter().enumerate().take(players) {
+        entities.push_back(Entity::new(x, y, EType::Player(id)));
+    }
+
+    // Enemy Ships
+    for _i in 0..scaled(with_rng(|r| r.gen_range(ships.0, ships.1 + 1))) {
+        let mut ship = place_random(&entities, EType::Ship);
+        ship.components.push(Component::new_vel());
+        entities.push_back(ship);
+    }
+    /*
+    let mut ship = Entity::new(6, 9, EType::Ship);
+    ship.components.push(Component::Velocity(1, 0));
+    entities.push_back(ship);
+    */
+
+    // HQ
+    let hq = place_random(&entities, EType::HQ);
+    entities.push_back(hq);
+
+    // Mines
+    for _i in 0..scaled(with_rng(|r| r.gen_range(mines.0, mines.1 + 1))) {
+        let mine = place_random(&entities, EType::Mine);
+        entities.push_back(mine);
+    }
+
+    // Sea Monsters
+    for _i in 0..scaled(difficulty.monsters) {
+        let mut monster = place_random(&entities, EType::Monster);
+        monster.components.push(Component::new_vel());
+        entities.push_back(monster);
+    }
+    entities
+}
+
+// Command words, and what they're short for.  Any unambiguous start of one
+// will do, as will the number from the menu.
+const COMMAND_WORDS: [(&str, Command); 15] = [
+    ("navigate", Command::Navigate),
+    ("sonar", Command::Sonar),
+    ("torpedo", Command::Torpedo),
+    ("missile", Command::Missile),
+    ("manuever", Command::Manuever),
+    ("maneuver", Command::Manuever),
+    ("dive", Command::Manuever),
+    ("depth", Command::Manuever),
+    ("status", Command::Status),
+    ("resupply", Command::Resupply),
+    ("sabotage", Command::Sabotage),
+    ("convert", Command::Convert),
+    ("surrender", Command::Surrender),
+    ("undo", Command::Undo),
+    ("log", Command::Log),
+];
+
+fn parse_command(word: &str) -> Result<Command, String> {
+    use Command::*;
+    if let Ok(v) = word.parse::<i32>() {
+        return match v {
+            0 => Ok(Navigate),
+            1 => Ok(Sonar),
+            2 => Ok(Torpedo),
+            3 => Ok(Missile),
+            4 => Ok(Manuever),
+            5 => Ok(Status),
+            6 => Ok(Resupply),
+            7 => Ok(Sabotage),
+            8 => Ok(Convert),
+            9 => Ok(Surrender),
+            _ => Err(tr!("order.no_such_command", v)),
+        };
+    }
+    let mut matches: Vec<Command> = COMMAND_WORDS.iter()
+        .filter(|w| w.0.starts_with(word))
+        .map(|w| w.1)
+        .collect();
+    matches.dedup();
+    match matches.len() {
+        0 => Err(tr!("order.unknown", word)),
+        1 => Ok(matches[0]),
+        _ => Err(tr!("order.ambiguous", word, COMMAND_WORDS.iter()
+                     .filter(|w| w.0.starts_with(word))
+                     .map(|w| w.0)
+                     .collect::<Vec<_>>()
+                     .join(&tr!("or")))),
+    }
+}
+
+// `what` is the catalog key naming what the number is for.
+fn parse_number<T: std::str::FromStr>(arg: &str, what: &str) -> Result<T, String> {
+    arg.parse::<T>().map_err(|_| tr!("order.not_a", arg, tr!(what)))
+}
+
+/// Read a whole order from one line, such as "nav 9 300" or "missile 3 fuel
+/// 900".  Case doesn't matter, and anything left off is asked for later.
+/// Numbers can be labelled: "power", "fuel", "men", "depth".
+pub fn parse_order(input: &str) -> Result<Order, String> {
+    let input = input.to_lowercase();
+    let mut words = input.split_whitespace().collect::<Vec<_>>();
+    if words.is_empty() {
+        return Err(tr!("order.none"));
+    }
+    let command = parse_command(words.remove(0))?;
+    // Labels are just for readability, except when converting, where they
+    // say which way.
+    if command != Command::Convert {
+        words.retain(|w| !["power", "fuel", "lbs", "men", "depth"].contains(w));
+    }
+    let mut args = words.into_iter().peekable();
+    let mut order = Order::new(command);
+    match command {
+        Command::Navigate | Command::Torpedo | Command::Missile => {
+            if let Some(arg) = args.next() {
+                let dir = parse_direction(arg)
+                    .ok_or_else(|| tr!("order.not_a_direction", arg))?;
+                order.direction = Some(dir);
+            }
+            if command != Command::Torpedo {
+                if let Some(arg) = args.next() {
+                    order.amount = Some(parse_number(arg, "order.quantity")?);
+                }
+            }
+            if command == Command::Missile {
+                if let Some(&arg) = args.peek() {
+                    if "yes".starts_with(arg) || arg == "confirm" {
+                        order.confirm = Some(true);
+                        args.next();
+                    }
+                }
+            }
+        }
+        Command::Manuever => {
+            if let Some(arg) = args.next() {
+                order.depth = Some(parse_number(arg, "order.depth")?);
+            }
+        }
+        Command::Sabotage => {
+            if let Some(arg) = args.next() {
+                order.amount = Some(parse_number(arg, "order.men")?);
+            }
+        }
+        Command::Log => {
+            if let Some(arg) = args.next() {
+                order.amount = Some(parse_number(arg, "order.turns")?);
+            }
+        }
+        Command::Convert => {
+            if let Some(arg) = args.next() {
+                order.conversion = match arg {
+                    "1" | "fuel" => Some(Conversion::FuelToPower),
+                    "2" | "power" => Some(Conversion::PowerToFuel),
+                    _ => return Err(tr!("order.conversion", arg)),
+                };
+                if let Some(arg) = args.next() {
+                    order.amount = Some(parse_number(arg, "order.quantity")?);
+                }
+            }
+        }
+        _ => {}
+    }
+    match args.next() {
+        Some(extra) => Err(tr!("order.extra", extra)),
+        None => Ok(order),
+    }
+}
+
+fn print_commands() {
+    outln!("{}", tr!("menu"));
+}
+
+// Explain why orders typed at the console couldn't be read.
+fn reject_orders(input: &str, reason: &str) {
+    outln!("{}.", reason);
+    // Only list the commands if that was what went wrong.
+    let word = input.split_whitespace().next().unwrap_or("");
+    if parse_command(&word.to_lowercase()).is_err() {
+        print_commands();
+    }
+}
+
+// Directions are laid out like a numeric keypad.
+fn parse_direction(input: &str) -> Option<(i8, i8)> {
+    match input.parse::<i32>() {
+        Ok(1) => Some((-1, 1)),
+        Ok(2) => Some((0, 1)),
+        Ok(3) => Some((1, 1)),
+        Ok(4) => Some((-1, 0)),
+        Ok(6) => Some((1, 0)),
+        Ok(7) => Some((-1, -1)),
+        Ok(8) => Some((0, -1)),
+        Ok(9) => Some((1, -1)),
+        _ => None
+    }
+}
+
+fn print_directions() {
+    outln!("{}", tr!("directions"));
+}
+
+fn get_direction() -> Option<(i8, i8)> {
+    // Prompt the player for a direction for navigation, sonar, or weapons.
+    loop {
+        let input = ask(&tr!("prompt.direction"))?;
+        if let Some(dir) = parse_direction(&input) {
+            return Some(dir);
+        }
+        print_directions();
+        outln!("{}", tr!("directions.cancel"));
+    }
+}
+
+/**********************************************************************************
+ * Command #0, navigate
+ *********************************************************************************/
+fn navigate(entities: &mut EntityColl, pi: &mut PlayerInfo, order: &Order) -> bool {
+    if pi.damage[SubSystem::Engines] < 0. {
+        outln!("{}", tr!("nav.engines_down", pi.name));
+        return false;
+    }
+    if pi.crew < rules().navigate_crew {
+        outln!("{}", tr!("nav.no_crew", pi.name));
+        return false;
+    }
+    let (dx, dy) = match order.direction.or_else(get_direction) {
+        Some(dir) => dir,
+        None => return belay(pi),
+    };
+    let p = match order.amount {
+        Some(p) if p <= pi.power => p,
+        Some(_) => {
+            outln!("{}", tr!("nav.no_power", pi.power, pi.name));
+            return false;
+        }
+        None => match get_power(pi.power) {
+            Some(p) => p,
+            None => return belay(pi),
+        },
+    };
+
+    let mut speed = 1.;
+    if pi.depth <= rules().shallow_depth {
+        speed -= 0.23 + rnd()/10.;
+    }
+    if p > rules().safe_power && rnd() >= rules().pile_safety {
+        outln!("{}", tr!("nav.supercritical", pi.name));
+        pi.die(Cause::Supercritical);
+    }
+    let range = ((p as f32) / rules().power_per_move as f32 * speed).round() as u32;
+
+    // extract player entity
+    let mut player = get_first(entities, EType::Player(pi.id)).unwrap();
+    let Position{mut x, mut y} = player.pos;
+    for step in 0..range {
+        pi.power = pi.power.saturating_sub(rules().power_per_move);
+        let (next_x, next_y) = match ocean().step(x, y, dx, dy) {
+            Some(next) => next,
+            None => {
+                outln!("{}", tr!("nav.edge", pi.name));
+                break;
+            }
+        };
+        if let Some(crashee) = get_collision(entities, next_x, next_y) {
+            use EType::*;
+            match crashee.etype {
+                Island => {
+                    outln!("{}", tr!("nav.aground", pi.name));
+                    break;
+                },
+                Ship => {
+                    outln!("{}", tr!("nav.rammed_ship"));
+                    pi.credit(Weapon::Ramming, 1);
+                    pi.die(Cause::Rammed);
+                },
+                HQ => {
+                    outln!("{}", tr!("nav.rammed_hq"));
+                    pi.record.hq_destroyed = true;
+                    pi.die(Cause::Rammed);
+                },
+                Mine => {
+                    outln!("{}", tr!("nav.mine", pi.name));
+                    pi.die(Cause::Mine);
+                },
+                Monster => {
+                    if rnd() >= rules().monster_escape {
+                        outln!("{}", tr!("nav.eaten", pi.name));
+                        pi.die(Cause::Monster);
+                    } else {
+                        // Note:  In this case, the monster and the player
+                        // occupy the same position.  In the original game, if
+                        // this was the final movement of the player, the sea
+                        // monster would be eliminated.
+                        // Otherwise, I'm unconditionally eliminating the sea
+                        // monster by not putting the entity back into the pool.
+                        outln!("{}", tr!("nav.rammed_monster"));
+                        if rules().classic {
+                            x = next_x;
+                            y = next_y;
+                            if step + 1 < range {
+                                entities.push_back(crashee);
+                            }
+                        }
+                    }
+                },
+                Player(id) => {
+                    if id == pi.id {
+                        panic!("How did you ram yourself?!?!");
+                    }
+                    outln!("{}", tr!("nav.rammed_sub"));
+                    pi.die(Cause::Rammed);
+                }
+            }
+        } else {
+            x = next_x;
+            y = next_y;
+
+            if nearby_monsters(entities, x, y) {
+                outln!("{}", tr!("nav.eaten_passing", pi.name));
+                pi.die(Cause::Monster);
+            }
+        }
+        if !pi.alive {
+            break;
+        }
+    }
+    if pi.alive {
+        player.pos = Position {x, y};
+        entities.push_back(player);
+    }
+    true
+}
+
+// Return true if the player was eaten by a nearby sea monster
+fn nearby_monsters(entities: &EntityColl, x: usize, y: usize) -> bool {
+    let mut nearby = false;
+    // For each sea monster within reach, a chance to be eaten.
+    let pos = Position {x, y};
+    let reach = rules().monster_reach;
+    for e in entities {
+        if e.etype == EType::Monster && pos.distance_within(&e.pos, reach).is_some() {
+            nearby = true;
+            if rnd() <= rules().monster_bite {
+                return true;
+            }
+        }
+    }
+    if nearby {
+        outln!("{}", tr!("nav.narrow_escape"));
+    }
+    false
+}
+
+
+fn get_power(avail: u32) -> Option<u32> {
+    let prompt_str = &tr!("prompt.power", avail);
+    loop {
+        let input = ask(prompt_str)?;
+        match input.parse::<u32>() {
+            Ok(p) if p <= avail => return Some(p),
+            _ => outln!("{}", tr!("prompt.power.retry", avail)),
+        }
+    }
+}
+
+/**********************************************************************************
+ * Command #1, sonar
+ *********************************************************************************/
+fn sonar(entities: &EntityColl, pi: &mut PlayerInfo, order: &Order) -> bool {
+    if pi.damage[SubSystem::Sonar] < 0. {
+        outln!("{}", tr!("sonar.down"));
+    } else if pi.crew < rules().sonar_crew {
+        outln!("{}", tr!("sonar.no_crew"));
+    } else {
+        // TODO:  linear vs map.
+        let mut chart = Chart::new(pi.id);
+        for e in entities.iter() {
+            // TODO:  Sonar noise
+            chart.mark(&e.pos, e.etype.clone());
+        }
+
+        let ppos = get_first_pos(entities, EType::Player(pi.id)).unwrap();
+        let mut view = Viewport::centred(&chart, &ppos);
+        if accessible() {
+            view::describe(&chart, &ppos);
+        } else {
+            view.draw(&chart);
+        }
+        if order.interactive && !accessible() && chart.pannable() {
+            loop {
+                let input = prompt(&tr!("prompt.pan"));
+                if input.is_empty() {
+                    break;
+                } else if input.starts_with('m') || input.starts_with('M') {
+                    view::draw_overview(&chart, view::VIEW_WIDTH);
+                } else if let Some((dx, dy)) = parse_direction(&input) {
+                    view.pan(&chart, dx, dy);
+                    view.draw(&chart);
+                } else {
+                    print_directions();
+                }
+            }
+        }
+        pi.log.note_contacts(&chart, pi.id);
+        pi.last_sonar = Some(chart);
+        // Same power cost for map and linear sonar
+        pi.power = pi.power.saturating_sub(rules().sonar_power);
+    }
+    false
+}
+
+/******************************************************************************
+ * Command #2, torpedo control
+ ******************************************************************************/
+fn fire_torpedo(entities: &mut EntityColl, pi: &mut PlayerInfo, order: &Order) -> bool {
+    let mut turn_over = false;
+    if pi.damage[SubSystem::Torpedos] < 0. {
+        outln!("{}", tr!("torpedo.down", pi.name));
+    } else if pi.crew < rules().torpedo_crew {
+        outln!("{}", tr!("torpedo.no_crew", pi.name));
+    } else if pi.torpedos == 0 {
+        outln!("{}", tr!("torpedo.none", pi.name));
+    } else if pi.depth >= rules().deep_depth && rnd() > rules().deep_torpedo_safety {
+        outln!("{}", tr!("torpedo.implode"));
+        pi.die(Cause::Crushed);
+        turn_over = true;
+    } else {
+        let (dx, dy) = match order.direction.or_else(get_direction) {
+            Some(dir) => dir,
+            None => return belay(pi),
+        };
+        turn_over = true;
+        pi.torpedos -= 1;
+        pi.record.torpedos_fired += 1;
+        pi.power = pi.power.saturating_sub(rules().torpedo_power);
+
+        // Note:  Docs say range is 7-13, but equation below does not match.
+        // It's the original game's, so classic rules play by it too.
+        let rules = rules();
+        let mut range = rules.torpedo_range - (rnd()*rules.torpedo_spread as f32).round() as i32;
+        if pi.depth > rules.shallow_depth {
+            range += rules.deep_torpedo_bonus;
+        }
+
+        let mut success = false;
+        let start = get_first_pos(entities, EType::Player(pi.id)).unwrap();
+        let Position{mut x, mut y} = start;
+        for i in 0..range {
+            match ocean().step(x, y, dx, dy) {
+                Some(next) => { x = next.0; y = next.1; }
+                None => {
+                    outln!("{}", tr!("torpedo.out_of_range", pi.name));
+                    break;
+                }
+            }
+            // On a small wrapped ocean, the torpedo can come all the way
+            // round.  Luckily it's not armed to hit its own sub.
+            if start == (Position {x, y}) {
+                outln!("{}", tr!("torpedo.circled", pi.name));
+                break;
+            }
+
+            // Add some suspense
+            if !accessible() {
+                out!("..{}..\r", i);
+            }
+            pause(500);
+
+            if let Some(e) = get_collision(entities, x, y) {
+                resolve_torpedo(e, entities, pi);
+                success = true;
+                break;
+            }
+        }
+        if !success {
+            outln!("{}", tr!("torpedo.dud"));
+        }
+    }
+    turn_over
+}
+
+fn resolve_torpedo(e: Entity, entities: &mut EntityColl, pi: &mut PlayerInfo) {
+    use EType::*;
+    match e.etype {
+        Player(id) => {
+            if id == pi.id {
+                panic!("How did you torpedo yourself?!?");
+            }
+            outln!("{}", tr!("torpedo.sub", pi.name));
+        }
+        Island => {
+            outln!("{}", tr!("torpedo.island", pi.name));
+        }
+        Ship => {
+            outln!("{}", tr!("torpedo.ship", pi.name));
+            pi.credit(Weapon::Torpedo, 1);
+        }
+        Mine => {
+            outln!("{}", tr!("torpedo.mine"));
+            entities.push_back(e);
+        }
+        HQ => {
+            outln!("{}", tr!("torpedo.hq", pi.name));
+            pi.record.hq_destroyed = true;
+        }
+        Monster => {
+            outln!("{}", tr!("torpedo.monster"));
+            entities.push_back(e);
+        }
+    }
+}
+
+/**********************************************************************************
+ * Command #3, Polaris Missiles
+ *********************************************************************************/
+fn fire_missile(entities: &mut EntityColl, pi: &mut PlayerInfo, order: &Order) -> bool {
+    if pi.damage[SubSystem::Missiles] < 0. {
+        outln!("{}", tr!("missile.down", pi.name));
+        return false;
+    }
+    if pi.crew < rules().missile_crew {
+        outln!("{}", tr!("missile.no_crew", pi.name));
+        return false;
+    }
+    if pi.missiles == 0 {
+        outln!("{}", tr!("missile.none", pi.name));
+        return false;
+    }
+    if let Some(fuel) = order.amount {
+        if fuel == 0 || fuel > pi.fuel {
+            outln!("{}", tr!("missile.fuel_left", pi.fuel, pi.name));
+            return false;
+        }
+    }
+    if pi.depth <= rules().shallow_depth || pi.depth >= rules().deep_depth {
+        let proceed = order.confirm.unwrap_or_else(|| {
+            let input = prompt(&tr!("prompt.unsafe_launch")).to_lowercase();
+            input.starts_with(&tr!("answer.yes"))
+        });
+        if !proceed {
+            return belay(pi);
+        }
+        if rnd() >= rules().deep_missile_safety {
+            outln!("{}", tr!("missile.explodes", pi.name));
+            pi.die(Cause::Misfire);
+            return true;
+        }
+    }
+
+    let (dx, dy) = match order.direction.or_else(get_direction) {
+        Some(dir) => dir,
+        None => return belay(pi),
+    };
+    let Position{x, y} = get_first_pos(entities, EType::Player(pi.id)).unwrap();
+    let fuel = match order.amount.or_else(|| get_fuel(pi)) {
+        Some(fuel) => fuel,
+        None => return belay(pi),
+    };
+    pi.fuel -= fuel;
+    pi.missiles -= 1;
+    pi.record.fuel_used += fuel;
+    pi.record.missiles_fired += 1;
+    let range = (fuel as f32 / rules().fuel_per_cell).round() as i32;
+    match ocean().offset(x, y, dx, dy, range) {
+        Some((mx, my)) => resolve_missile(mx, my, entities, pi),
+        None => outln!("{}", tr!("missile.lost", pi.name)),
+    }
+    true
+}
+
+fn get_fuel(pi: &PlayerInfo) -> Option<u32> {
+    loop {
+        let input = ask(&tr!("prompt.fuel"))?;
+        match input.parse::<u32>() {
+            Ok(fuel) if fuel > 0 && fuel <= pi.fuel => return Some(fuel),
+            _ => outln!("{}", tr!("prompt.fuel.retry", pi.fuel, pi.name)),
+        }
+    }
+}
+
+fn resolve_missile(x: usize, y: usize,
+                   entities: &mut EntityColl, pi: &mut PlayerInfo) {
+    let pos = Position {x, y};
+    // Classic rules kill the player before anything else is hit, so there's
+    // no sinking the last ships on the way out.
+    if rules().classic {
+        let own = get_first_pos(entities, EType::Player(pi.id));
+        if own.is_some_and(|own| pos.distance_within(&own, 1.).is_some()) {
+            outln!("{}", tr!("missile.self", pi.name));
+            entities.retain(|e| e.etype != EType::Player(pi.id));
+            pi.die(Cause::Misfire);
+            return;
+        }
+    }
+    let (mut monsters, mut ships, mut mines, mut island) = (0, 0, 0, 0);
+    for _i in 0..entities.len() {
+        let e = entities.pop_front().unwrap();
+        if pos.distance_within(&e.pos, 1.).is_some() {
+            use EType::*;
+            match e.etype {
+                Player(id) if id == pi.id => {
+                    outln!("{}", tr!("missile.self", pi.name));
+                    pi.die(Cause::Misfire);
+                    // Note:  Original code would kill player instantly.
+                    // I'm going to allow the possibility of a draw.
+                }
+                Player(_) => {
+                    outln!("{}", tr!("missile.sub", pi.name));
+                }
+                Island => island += 1,
+                Ship => ships += 1,
+                Mine => mines += 1,
+                HQ => {
+                    outln!("{}", tr!("missile.hq", pi.name));
+                    pi.record.hq_destroyed = true;
+                }
+                Monster => monsters += 1,
+            }
+        } else {
+            entities.push_back(e);
+        }
+    }
+    if island > 0 {
+        outln!("{}", tr!("missile.island", pi.name));
+    }
+    if mines > 0 {
+        outln!("{}", tr!("missile.mines", mines, pi.name));
+    }
+    if monsters > 0 {
+        outln!("{}", tr!("missile.monsters", monsters, pi.name));
+    }
+    if ships > 0 {
+        outln!("{}", tr!("missile.ships", ships, pi.name));
+        pi.credit(Weapon::Missile, ships);
+    }
+}
+
+/*******************************************************************************
+ * Command #4, Manuever
+ ******************************************************************************/
+fn manuever(pi: &mut PlayerInfo, order: &Order) -> bool {
+    if pi.damage[SubSystem::Computers] < 0. {
+        outln!("{}", tr!("dive.down", pi.name));
+        return false;
+    }
+    if pi.crew < rules().manuever_crew {
+        outln!("{}", tr!("dive.no_crew", pi.name));
+        return false;
+    }
+    let depth = match order.depth.or_else(get_depth) {
+        Some(depth) => depth,
+        None => return belay(pi),
+    };
+    if (0..rules().crush_depth).contains(&depth) {
+        let power_used = (depth - pi.depth).unsigned_abs().div_ceil(rules().feet_per_power);
+        pi.power = pi.power.saturating_sub(power_used);
+        pi.depth = depth;
+    } else {
+        outln!("{}", tr!("dive.crushed", pi.name));
+        pi.die(Cause::Crushed);
+    }
+    true
+}
+
+fn get_depth() -> Option<i32> {
+    loop {
+        let input = ask(&tr!("prompt.depth"))?;
+        match input.parse::<i32>() {
+            Ok(depth) => return Some(depth),
+            Err(_) => outln!("{}", tr!("prompt.depth.retry")),
+        }
+    }
+}
+
+/*******************************************************************************
+ * Command #5, status report
+ ******************************************************************************/
+fn status_report(entities: &EntityColl, pi: &PlayerInfo, progress: &Progress) -> bool {
+    let ppos = get_first_pos(entities, EType::Player(pi.id)).unwrap();
+    if pi.damage[SubSystem::Computers] < 0. {
+        outln!("{}", tr!("status.no_reports", pi.name));
+    } else if pi.crew < rules().status_crew {
+        outln!("{}", tr!("status.no_one", pi.name));
+    } else {
+        outln!();
+        outln!("{}", tr!("status.supplies", count_all_of(entities, EType::Ship), pi.power,
+                         pi.torpedos, pi.missiles, pi.crew, pi.fuel));
+        outln!();
+        outln!("{}", tr!("status.systems"));
+        for (key, value) in pi.damage {
+            outln!("    {:12} {:2.4}", key.name(), value);
+        }
+        if let Some(ref chart) = pi.last_sonar {
+            outln!();
+            outln!("{}", tr!("status.last_sonar"));
+            if accessible() {
+                view::describe(chart, &ppos);
+            } else {
+                view::draw_overview(chart, view::STATUS_OVERVIEW);
+            }
+        }
+    }
+    outln!("{}", tr!("status.position", format!("{:?}", ppos), pi.depth));
+    outln!("{}", tr!("status.turn", progress.turn));
+    if !progress.mission.standard() {
+        let situation = situation(entities, progress.others + pi.ships_sunk, &[pi],
+                                  progress.turn, false);
+        progress.mission.progress(&situation, &ppos);
+    }
+    false
+}
+
+/******************************************************************************
+ * Command #6, resupply from HQ
+ ******************************************************************************/
+fn resupply(entities: &EntityColl, pi: &mut PlayerInfo) -> bool {
+    let mut turn_over = false;
+    if pi.damage[SubSystem::Resupply] < 0. {
+        outln!("{}", tr!("resupply.hatch", pi.name));
+    } else if pi.resupply_left == 0 {
+        outln!("{}", tr!("resupply.abandoned"));
+    } else {
+        let ppos = get_first_pos(entities, EType::Player(pi.id)).unwrap();
+        let rules = rules();
+        if let Some(hqpos) = get_first_pos(entities, EType::HQ) {
+            if ppos.distance(&hqpos) <= rules.docking_range && pi.depth <= rules.shallow_depth {
+                // Original code is unconditional, which could result in having
+                // fewer supplies after resupplying.  Classic rules keep it so.
+                if rules.classic {
+                    pi.power = rules.resupply_power;
+                    pi.torpedos = rules.resupply_torpedos;
+                    pi.missiles = rules.resupply_missiles;
+                    pi.fuel = rules.resupply_fuel;
+                    pi.crew = rules.resupply_crew;
+                } else {
+                    pi.power = pi.power.max(rules.resupply_power);
+                    pi.torpedos = pi.torpedos.max(rules.resupply_torpedos);
+                    pi.missiles = pi.missiles.max(rules.resupply_missiles);
+                    pi.fuel = pi.fuel.max(rules.resupply_fuel);
+                    pi.crew = pi.crew.max(rules.resupply_crew);
+                }
+                outln!("{}", tr!("resupply.divers"));
+                pi.resupply_left -= 1;
+                pi.record.resupplies += 1;
+                turn_over = true;
+            }
+        }
+        if !turn_over {
+            outln!("{}", tr!("resupply.too_far", pi.name));
+        }
+    }
+    turn_over
+}
+
+/******************************************************************************
+ * Command #7, sabotage surrounding ships
+ ******************************************************************************/
+fn sabotage(entities: &mut EntityColl, pi: &mut PlayerInfo, order: &Order) -> bool {
+    let mut turn_over = false;
+    if pi.damage[SubSystem::Sabotage] < 0. {
+        outln!("{}", tr!("sabotage.hatches", pi.name));
+    } else if pi.crew < rules().sabotage_crew {
+        outln!("{}", tr!("sabotage.no_crew", pi.name));
+    } else if order.amount == Some(0) {
+        outln!("{}", tr!("sabotage.nobody", pi.name));
+    } else if order.amount.is_some_and(|v| pi.crew < rules().skeleton_crew + v) {
+        outln!("{}", tr!("skeleton_crew", rules().skeleton_crew, pi.name));
+    } else {
+        let ppos = get_first_pos(entities, EType::Player(pi.id)).unwrap();
+        let mut ships = Vec::new();
+        let mut nearby_monsters = false;
+        for _ in 0..entities.len() {
+            let e = entities.pop_front().unwrap();
+            if e.pos.in_range(&ppos, rules().sabotage_range) {
+                if e.etype == EType::Ship {
+                    ships.push(e);
+                } else {
+                    if e.etype == EType::Monster {
+                        nearby_monsters = true;
+                    }
+                    entities.push_back(e);
+                }
+            } else {
+                entities.push_back(e);
+            }
+        }
+        if !ships.is_empty() {
+            outln!("{}", tr!("sabotage.in_range", ships.len(), pi.name));
+            // Q1 in original code
+            let men = match order.amount.or_else(|| get_men(pi)) {
+                Some(men) => men as f32,
+                None => {
+                    entities.extend(ships);
+                    return belay(pi);
+                }
+            };
+            turn_over = true;
+            // Sink Ships
+            // D3 in original code
+            let ship_count = ships.len() as f32;
+            let ratio = ship_count / men;
+            outln!("{}", tr!("sabotage.ratio", ratio));
+            // D6 in original code
+            let mut ships_sunk = 0;
+            for e in ships {
+                // Not sure why we need two rnd() calls here
+                if ratio > 1. - rnd() && rnd() + ratio < 0.9 {
+                    entities.push_back(e);
+                } else {
+                    ships_sunk += 1;
+                }
+            }
+            outln!("{}", tr!("sabotage.sunk", ships_sunk, pi.name));
+            pi.credit(Weapon::Sabotage, ships_sunk);
+            
+            // See how many men return safely.
+            let mut men_lost = 0;
+            for _ in 0..men as i32 {
+                if rnd() > rules().saboteur_survival {
+                    men_lost += 1;
+                }
+            }
+            if nearby_monsters {
+                let mut men_eaten = 0;
+                for _ in 0..men as u32 - men_lost {
+                    if rnd() < rules().saboteur_eaten {
+                        men_eaten += 1;
+                    }
+                }
+                outln!("{}", tr!("sabotage.monster", men_eaten, pi.name));
+                pi.crew -= men_eaten;
+                pi.record.crew_lost += men_eaten;
+            }
+            outln!("{}", tr!("sabotage.accidents", men_lost, pi.name));
+            pi.crew -= men_lost;
+            pi.record.crew_lost += men_lost;
+        } else {
+            outln!("{}", tr!("sabotage.out_of_range", pi.name));
+        }
+    }
+    turn_over
+}
+
+fn get_men(pi: &PlayerInfo) -> Option<u32> {
+    let prompt_str = &tr!("prompt.men", pi.name);
+    loop {
+        let input = ask(prompt_str)?;
+        match input.parse::<u32>() {
+            Ok(v) if v > 0 && pi.crew >= rules().skeleton_crew + v => return Some(v),
+            Ok(v) if v > 0 => outln!("{}", tr!("skeleton_crew", rules().skeleton_crew, pi.name)),
+            _ => outln!("{}", tr!("prompt.men.retry", pi.crew - rules().skeleton_crew)),
+        }
+    }
+}
+
+/*******************************************************************************
+ * Command #8, Convert power to fuel or fuel to power
+ ******************************************************************************/
+fn convert_power_or_fuel(pi: &mut PlayerInfo, order: &Order) -> bool {
+    if pi.damage[SubSystem::Converter] < 0. {
+        outln!("{}", tr!("convert.down", pi.name));
+        return false;
+    }
+    if pi.crew < rules().convert_crew {
+        outln!("{}", tr!("convert.no_crew", pi.name));
+        return false;
+    }
+
+    let conversion = order.conversion.or_else(|| {
+        loop {
+            let input = ask(&tr!("prompt.conversion"))?;
+            if input == "1" {
+                return Some(Conversion::FuelToPower);
+            } else if input == "2" {
+                return Some(Conversion::PowerToFuel);
+            }
+            outln!("{}", tr!("prompt.conversion.retry"));
+        }
+    });
+    let conversion = match conversion {
+        Some(conversion) => conversion,
+        None => return belay(pi),
+    };
+    let converted = match conversion {
+        Conversion::FuelToPower => convert_fuel_to_power(pi, order.amount),
+        Conversion::PowerToFuel => convert_power_to_fuel(pi, order.amount),
+    };
+    if !converted {
+        return false;
+    }
+    outln!("{}", tr!("convert.done", pi.power, pi.fuel));
+    true
+}
+
+fn convert_power_to_fuel(pi: &mut PlayerInfo, amount: Option<u32>) -> bool {
+    if pi.power == 0 {
+        outln!("{}", tr!("convert.no_power", pi.name));
+        return false;
+    }
+    let prompt_str = &tr!("prompt.convert_power", pi.power - 1);
+    let power = match amount {
+        Some(power) if power < pi.power => power,
+        Some(_) => {
+            outln!("{}", tr!("convert.no_power", pi.name));
+            return false;
+        }
+        None => loop {
+            let input = match ask(prompt_str) {
+                Some(input) => input,
+                None => return belay(pi),
+            };
+            match input.parse::<u32>() {
+                Ok(power) if power < pi.power => break power,
+                _ => outln!("{}", tr!("prompt.convert.retry", pi.power - 1)),
+            }
+        }
+    };
+    pi.power -= power;
+    pi.fuel += power * rules().fuel_per_power;
+    true
+}
+
+fn convert_fuel_to_power(pi: &mut PlayerInfo, amount: Option<u32>) -> bool {
+    let prompt_str = &tr!("prompt.convert_fuel", pi.fuel);
+    let fuel = match amount {
+        Some(fuel) if fuel <= pi.fuel => fuel,
+        Some(_) => {
+            outln!("{}", tr!("convert.no_fuel", pi.name));
+            return false;
+        }
+        None => loop {
+            let input = match ask(prompt_str) {
+                Some(input) => input,
+                None => return belay(pi),
+            };
+            match input.parse::<u32>() {
+                Ok(fuel) if fuel <= pi.fuel => break fuel,
+                _ => outln!("{}", tr!("prompt.convert.retry", pi.fuel)),
+            }
+        }
+    };
+    pi.fuel -= fuel;
+    pi.record.fuel_used += fuel;
+    pi.power += fuel / rules().fuel_per_power;
+    true
+}
+
+/*******************************************************************************
+ * Command #9, surrender
+ ******************************************************************************/
+fn surrender(pi: &mut PlayerInfo) -> bool {
+    outln!("{}", tr!("surrender", pi.name));
+    pi.die(Cause::Surrendered);
+    true
+}
+
+/*******************************************************************************
+ * Enemy movement
+ ******************************************************************************/
+// Subs and ships lost as the enemies move are removed from the pool, and
+// returned along with what got them.  See bury_the_dead.
+fn move_enemies(entities: &mut EntityColl) -> Vec<(EType, Cause)> {
+    let mut casualties = Vec::new();
+    let mut moved = EntityColl::with_capacity(entities.len());
+    let mut unmoved = EntityColl::with_capacity(entities.len());
+
+    // Non-moving entities get precedence
+    // Might be able to ensure this based on initial order and
+    // the generic movement function.
+    while let Some(e) = entities.pop_front() {
+        if e.components.is_empty() {
+            moved.push_back(e);
+        } else {
+            unmoved.push_back(e);
+        }
+    }
+
+    while !unmoved.is_empty() {
+        let unmoved_len = unmoved.len();
+        for _i in 0..unmoved_len {
+            let e = unmoved.pop_front().unwrap();
+            if let Some(casualty) = move_enemy(e, &mut unmoved, &mut moved) {
+                casualties.push(casualty);
+            }
+        }
+        if unmoved_len == unmoved.len() {
+            // Either un-moved entities are trying to move through
+            // each other, or an un-moved entity is blocked by moved
+            // entities.
+            outln!("{}", tr!("stalemate"));
+            // Change direction of remaining unmoved entities
+            for e in &mut unmoved {
+                e.components[0] = Component::new_vel();
+                // outln!("New velocity: {:?}", e.components[0]);
+            }
+            // Better luck next time
+            break;
+        }
+    }
+    // TODO:  Filter out dead entities
+    entities.extend(moved);
+    entities.extend(unmoved);
+    casualties
+}
+
+fn resolve_collision(e: &Entity, crashee: &Entity) -> EResolution {
+    use EType::*;
+    use EResolution::*;
+    match e.etype {
+        Ship => match crashee.etype {
+            Island => {
+                outln!("{}", tr!("collision.ship_island"));
+                MoverChangeDirection
+            },
+            Ship => {
+                outln!("{}", tr!("collision.ship_ship"));
+                MoverChangeDirection
+            },
+            Player(_) => {
+                outln!("{}", tr!("collision.rammed"));
+                CrasheeDestroyed
+            },
+            HQ => {
+                outln!("{}", tr!("collision.hq_rammed"));
+                CrasheeDestroyed
+            },
+            Mine => {
+                if rnd() < rules().ship_mine_avoidance {
+                    outln!("{}", tr!("collision.ship_mine"));
+                    MoverChangeDirection
+                }
+                else {
+                    outln!("{}", tr!("collision.ship_mined"));
+                    MoverDestroyed
+                }
+            },
+            Monster => {
+                outln!("{}", tr!("collision.ship_eaten"));
+                MoverDestroyed
+            }
+        }
+        Monster => match crashee.etype {
+            Island => {
+                outln!("{}", tr!("collision.monster_island"));
+                MoverChangeDirection
+            },
+            Player(_) => {
+                outln!("{}", tr!("collision.eaten"));
+                CrasheeDestroyed
+            },
+            HQ => {
+                outln!("{}", tr!("collision.hq_eaten"));
+                CrasheeDestroyed
+            },
+            Ship => {
+                outln!("{}", tr!("collision.ship_eaten_moving"));
+                CrasheeDestroyed
+            },
+            Mine => {
+                outln!("{}", tr!("collision.monster_mined"));
+                MoverDestroyed
+            },
+            Monster => {
+                outln!("{}", tr!("collision.monster_fight"));
+                if rnd() < rules().monster_standoff {
+                    outln!("{}", tr!("collision.tie"));
+                    MoverChangeDirection
+                } else {
+                    outln!("{}", tr!("collision.one_dies"));
+                    MoverDestroyed
+                }
+            }
+        }
+        _ => panic!("Unexpected mover type {:?}", e.etype)
+    }
+}
+
+/// Returns the sub or ship destroyed by the move, if any, and the cause.
+fn move_enemy(e: Entity, unmoved: &mut EntityColl, moved: &mut EntityColl)
+              -> Option<(EType, Cause)> {
+    let mut casualty = None;
+    // Calculate destination
+    let (dx, dy) = match e.components[0] {
+        Component::Velocity(dx, dy) => (dx, dy),
+        _ => panic!("{:?} has no velocity", e.etype),
+    };
+    let (x, y) = match ocean().step(e.pos.x, e.pos.y, dx, dy) {
+        Some(dest) => dest,
+        None => {
+            outln!("{}", tr!("collision.edge", view::nouns(&e.etype).0));
+            moved.push_back(change_direction(e));
+            return None;
+        }
+    };
+    if check_collision(unmoved, x, y) {
+        // Might be able to move later
+        unmoved.push_back(e);
+    } else {
+        // If collision in moved, we have to resolve
+        match get_collision(moved, x, y) {
+            Some(crashee) => {
+                use EResolution::*;
+                match resolve_collision(&e, &crashee) {
+                    CrasheeDestroyed => {
+                        // Special-case handling of sub and ship destruction
+                        if matches!(crashee.etype, EType::Player(_) | EType::Ship) {
+                            let cause = if e.etype == EType::Monster {
+                                Cause::Monster
+                            } else {
+                                Cause::Rammed
+                            };
+                            casualty = Some((crashee.etype.clone(), cause));
+                        }
+                        let mut moved_entity = e;
+                        moved_entity.pos = Position {x, y};
+                        moved.push_back(moved_entity);
+                    },
+
+                    MoverDestroyed => {
+                        if e.etype == EType::Ship {
+                            let cause = if crashee.etype == EType::Mine {
+                                Cause::Mine
+                            } else {
+                                Cause::Monster
+                            };
+                            casualty = Some((EType::Ship, cause));
+                        }
+                        moved.push_back(crashee);
+                    }
+
+                    MoverChangeDirection => {
+                        unmoved.push_back(change_direction(e));
+                        moved.push_back(crashee);
+                    }
+                }
+            },
+            None => {
+                // No collision, move.
+                let mut moved_entity = e;
+                moved_entity.pos = Position {x, y};
+                // outln!("Moving entity: {:?}", moved_entity);
+                moved.push_back(moved_entity);
+            }
+        }
+    }
+    casualty
+}
+
+/**********************************************************************************
+ * Enemy attacks
+ *********************************************************************************/
+// Threat is the most that does no real damage, then light, moderate and heavy
+// damage.  Anything more is critical.
+fn retaliation(entities: &EntityColl, pi: &mut PlayerInfo, threat_levels: &[f32; 4]) {
+    let mut threat = 0f32;
+    let ppos = get_first_pos(entities, EType::Player(pi.id)).unwrap();
+    for e in entities {
+        if e.etype == EType::Ship {
+            if let Some(dist) = ppos.distance_within(&e.pos, rules().ship_range) {
+                if accessible() {
+                    outln!("{}", tr!("attack.firing_bearing", view::bearing(&ppos, &e.pos)));
+                } else {
+                    outln!("{}", tr!("attack.firing", format!("{:?}", e.pos)));
+                }
+                threat += rnd() / dist;
+            }
+        }
+    }
+    outln!("{}", tr!("attack.threat", threat));
+
+    let mut power_drain = 0;
+    let mut system_count = 0;
+    let mut damage = 0f32;
+    let mut severity = "no";
+
+    if threat != 0. {
+        pi.under_fire = true;
+        let side = if rnd() > 0.5 { tr!("port") } else { tr!("starboard") };
+        outln!("{}", tr!("attack.depth_charges", side, pi.name));
+        let rules = rules();
+        // Power lost to a hit: some, and up to spread more.
+        let drain = |power: u32, spread: u32| {
+            if spread == 0 { power } else { power + (rnd()*spread as f32) as u32 }
+        };
+        if threat <= threat_levels[0] && rnd() <= rules.charge_miss {
+            outln!("{}", tr!("attack.no_damage", pi.name));
+        } else if threat <= threat_levels[1] && rnd() <= rules.light_hit_chance {
+            outln!("{}", tr!("attack.light", pi.name));
+            severity = "light";
+            power_drain = drain(rules.light_hit_power, rules.light_hit_spread);
+            system_count = rules.light_hit_systems;
+            damage = rules.light_hit_damage;
+        } else if threat <= threat_levels[2] && rnd() <= rules.moderate_hit_chance {
+            outln!("{}", tr!("attack.moderate", pi.name));
+            severity = "moderate";
+            power_drain = drain(rules.moderate_hit_power, rules.moderate_hit_spread);
+            system_count = rules.moderate_hit_systems;
+            damage = rules.moderate_hit_damage;
+        } else if threat <= threat_levels[3] && rnd() <= rules.heavy_hit_chance {
+            outln!("{}", tr!("attack.heavy", pi.name));
+            severity = "heavy";
+            power_drain = drain(rules.heavy_hit_power, rules.heavy_hit_spread);
+            system_count = rules.heavy_hit_systems;
+            damage = rules.heavy_hit_damage;
+        } else {
+            outln!("{}", tr!("attack.critical"));
+            if accessible() {
+                outln!("{}", tr!("attack.garbled"));
+            } else {
+                out!("QOIJ");
+                pause(500);
+                out!("XXXX");
+            }
+            severity = "critical";
+            power_drain = drain(rules.critical_hit_power, rules.critical_hit_spread);
+            system_count = rules.critical_hit_systems;
+            damage = rules.critical_hit_damage;
+        }
+    }
+
+    let power = pi.power.min(power_drain);
+    pi.power -= power;
+    let mut systems = Vec::new();
+    for _ in 0..system_count {
+        let damaged_system: SubSystem = with_rng(|r| r.gen());
+        let hit = rnd() * damage;
+        pi.damage[damaged_system] -= hit;
+        pi.record.damage[damaged_system] += hit;
+        systems.push(damaged_system);
+    }
+    if threat != 0. {
+        pi.log.note(Event::Hit { severity, power, systems });
+    }
+}
+
+// Rate is how quickly repairs get done, 1 being normal.
+fn repair(pi: &mut PlayerInfo, rate: f32) {
+    for (key, value) in pi.damage {
+        if value < 3. {
+            let mut repair = rnd() * (2.+rnd()*2.) * rate;
+            if pi.depth <= rules().shallow_depth || pi.depth > rules().deep_depth {
+                repair *= 2.;
+            }
+            pi.damage[key] = value + repair;
+        }
+    }
+}
+
+// Most subs in one game.
+pub const MAX_PLAYERS: usize = 4;
+
+// Hot-seat games share one terminal, so clear away the last captain's
+// reports before the next one sits down.
+fn hand_over(pi: &PlayerInfo) {
+    out!("\x1b[2J\x1b[H");
+    prompt(&tr!("prompt.hand_over", pi.name));
+    out!("\x1b[2J\x1b[H");
+}
+
+// Carry out an order.  Returns true if it ends the turn.
+fn execute(entities: &mut EntityColl, pi: &mut PlayerInfo, order: &Order,
+           progress: &Progress) -> bool {
+    use Command::*;
+    match order.command {
+        Navigate => navigate(entities, pi, order),
+        Sonar => sonar(entities, pi, order),
+        Torpedo => fire_torpedo(entities, pi, order),
+        Missile => fire_missile(entities, pi, order),
+        Manuever => manuever(pi, order),
+        Status => status_report(entities, pi, progress),
+        Resupply => resupply(entities, pi),
+        Sabotage => sabotage(entities, pi, order),
+        Convert => convert_power_or_fuel(pi, order),
+        Surrender => surrender(pi),
+        Log => {
+            pi.log.show(order.amount);
+            false
+        }
+        // Taken care of before getting here.
+        Undo => false,
+    }
+}
+
+// Most orders an automated captain can give in one turn.
+const MAX_ORDERS: u32 = 20;
+
+// Where things stand, in a sentence or two, for those who can't glance at
+// a status report.
+fn announce(pi: &PlayerInfo, turn: u32) {
+    outln!("{}", tr!("turn_summary", turn, pi.depth, pi.power, pi.fuel, pi.torpedos,
+                     pi.missiles, pi.crew));
+    let out_of_action: Vec<_> = pi.damage.iter()
+        .filter(|&(_, &health)| health < 0.)
+        .map(|(system, _)| system.name())
+        .collect();
+    if !out_of_action.is_empty() {
+        outln!("{}", tr!("under_repair", out_of_action.join(", ")));
+    }
+}
+
+// How the game is going, for the captain whose turn it is.
+struct Progress<'a> {
+    turn: u32,
+    mission: &'a Mission,
+    // Ships sunk by the other subs.
+    others: u32,
+}
+
+// One player's orders, up to the first command that ends the turn.  In
+// practice games, undoable is how many turns can be taken back, and true is
+// returned if the captain wants to.
+fn take_turn(entities: &mut EntityColl, pi: &mut PlayerInfo, captain: &mut dyn Captain,
+             progress: &Progress, undoable: Option<usize>) -> bool {
+    let turn = progress.turn;
+    let situation = situation(entities, progress.others + pi.ships_sunk, &[&*pi], turn, false);
+    if let Some(ships) = progress.mission.ships_to_sink(&situation) {
+        outln!("{}", tr!("ships_to_sink", ships, pi.name));
+    }
+    if let Some(limit) = progress.mission.turn_limit() {
+        outln!("{}", tr!("turns_left", limit + 1 - turn, pi.name));
+    }
+    if accessible() {
+        announce(pi, turn);
+    }
+    pi.log.start_turn(turn);
+    // Only depth charges take the last of the power between turns.
+    let drained = pi.power == 0;
+    let mut given = 0;
+    loop {
+        let position = get_first_pos(entities, EType::Player(pi.id)).unwrap();
+        let ships = count_all_of(entities, EType::Ship);
+        let order = captain.orders(&Observation { info: pi, position, turn, ships });
+        if order.command == Command::Undo {
+            match undoable {
+                Some(0) => outln!("{}", tr!("undo.nothing", pi.name)),
+                Some(_) => return true,
+                None => outln!("{}", tr!("undo.real_war", pi.name)),
+            }
+        } else {
+            let (before, sunk, noted) = (pi.supplies(), pi.ships_sunk, pi.log.entries.len());
+            let turn_over = execute(entities, pi, &order, progress);
+            let after = pi.supplies();
+            // Orders that were refused or called off don't make the log.
+            if turn_over || before != after {
+                let sunk = pi.ships_sunk - sunk;
+                pi.log.note_before(noted, Event::Order { command: order.command, before, after,
+                                                         sunk });
+            }
+            if turn_over {
+                break;
+            }
+        }
+        given += 1;
+        if captain.automated() && given >= MAX_ORDERS {
+            outln!("{}", tr!("crew_gives_up", pi.name));
+            break;
+        }
+    }
+    pi.under_fire = false;
+    // Various commands use power.  Maybe too much.
+    if pi.alive && pi.power == 0 {
+        outln!("{}", tr!("pile_dead"));
+        pi.die(if drained { Cause::Retaliation } else { Cause::PowerExhausted });
+    }
+    false
+}
+
+// Subs can be sunk by other players or by enemy movement, which only
+// removes the entity.  Likewise, a player can die while the sub remains.
+// Bring the two back in line.  Subs missing from the casualty list were
+// sunk by another player.
+fn bury_the_dead(entities: &mut EntityColl, players: &mut [PlayerInfo],
+                 casualties: &[(usize, Cause)]) {
+    for pi in players.iter_mut() {
+        let sub = get_first(entities, EType::Player(pi.id));
+        if pi.alive {
+            match sub {
+                Some(e) => entities.push_back(e),
+                None => {
+                    outln!("{}", tr!("sub_lost", pi.name));
+                    let cause = casualties.iter()
+                        .find(|c| c.0 == pi.id)
+                        .map_or(Cause::FriendlyFire, |c| c.1);
+                    pi.die(cause);
+                }
+            }
+        }
+    }
+}
+
+fn names<'a, I: Iterator<Item=&'a PlayerInfo>>(players: I) -> String {
+    players.map(|pi| pi.name.as_str()).collect::<Vec<_>>().join(&tr!("and"))
+}
+
+/// How a game turned out.
+pub struct Outcome {
+    pub turns: u32,
+    pub ships_left: u32,
+    pub players: Vec<PlayerInfo>,
+    pub wrecks: Wrecks,
+    pub difficulty: Difficulty,
+    pub mission: Mission,
+    // How the mission ended, if it did before the game.
+    pub verdict: Option<Verdict>,
+}
+
+impl Outcome {
+    /// Victory goes to the survivors of an accomplished mission.
+    pub fn won(&self) -> bool {
+        self.verdict == Some(Verdict::Accomplished)
+    }
+}
+
+/// How a game is to be played.
+#[derive(Clone, Debug, Default)]
+pub struct Settings {
+    // Each captain has a console of their own.
+    pub networked: bool,
+    // A practice game, where up to this many turns can be undone.
+    pub practice: Option<usize>,
+    // Supplies issued over the standard load, by sub.
+    pub issue: Vec<Supplies>,
+    pub difficulty: Difficulty,
+    pub mission: Mission,
+}
+
+// Everything needed to go back to the start of a turn.
+struct Snapshot {
+    turn: u32,
+    entities: EntityColl,
+    players: Vec<PlayerInfo>,
+    wrecks: Wrecks,
+    mission: Mission,
+    rng: XorShiftRng,
+}
+
+// How things stand for the mission.  Ships sunk by the subs, and the subs
+// afloat, are supplied, as a sub's wreck can linger until it's buried.
+// Enemy ships spot a sub alongside them, or one near the surface in range.
+fn situation(entities: &EntityColl, sunk: u32, afloat: &[&PlayerInfo], turn: u32,
+             round_over: bool) -> Situation {
+    let rules = rules();
+    let located: Vec<_> = afloat.iter()
+        .filter_map(|pi| get_first_pos(entities, EType::Player(pi.id)).map(|pos| (pos, pi.depth)))
+        .collect();
+    let ships = || entities.iter().filter(|e| e.etype == EType::Ship);
+    let spotted = |ship: &Position| located.iter().any(|&(ref sub, depth)| {
+        let range = if depth <= rules.shallow_depth { rules.ship_range } else { 1. };
+        sub.in_range(ship, range)
+    });
+    Situation {
+        turn,
+        round_over,
+        ships_left: count_all_of(entities, EType::Ship),
+        sunk,
+        flagship_afloat: ships()
+            .any(|e| e.components.iter().any(|c| matches!(c, Component::Flagship))),
+        hq_afloat: get_first_pos(entities, EType::HQ).is_some(),
+        detected: ships().any(|e| spotted(&e.pos)),
+        subs: located.iter().map(|l| l.0.clone()).collect(),
+    }
+}
+
+// Fill in the parts of the mission that depend on the ocean: which ship is
+// the flagship, and where the waypoint is.
+fn prepare(mission: &mut Mission, entities: &mut EntityColl) {
+    for goal in mission.goals.iter_mut() {
+        match *goal {
+            Goal::Flagship(None) => {
+                let flagship = entities.iter_mut().find(|e| {
+                    e.etype == EType::Ship &&
+                        !e.components.iter().any(|c| matches!(c, Component::Flagship))
+                });
+                if let Some(ship) = flagship {
+                    ship.components.push(Component::Flagship);
+                    *goal = Goal::Flagship(Some(ship.pos.clone()));
+                }
+            }
+            Goal::Waypoint(None) => {
+                *goal = Goal::Waypoint(Some(place_random(entities, EType::Island).pos));
+            }
+            _ => (),
+        }
+    }
+}
+
+fn sunk(players: &[PlayerInfo]) -> u32 {
+    players.iter().map(|pi| pi.ships_sunk).sum()
+}
+
+fn afloat(players: &[PlayerInfo]) -> Vec<&PlayerInfo> {
+    players.iter().filter(|pi| pi.alive).collect()
+}
+
+/// Play one game to the end, one sub per captain.
+pub fn play(captains: &mut [Box<dyn Captain>], settings: &Settings) -> Outcome {
+    let networked = settings.networked;
+    let difficulty = &settings.difficulty;
+    let mut entities = setup(captains.len(), difficulty);
+    let mut players: Vec<PlayerInfo> = captains.iter_mut().enumerate().map(|(id, captain)| {
+        console::focus(Some(id));
+        let mut pi = PlayerInfo::new(id, captain.name());
+        pi.resupply_left = difficulty.resupplies;
+        if let Some(extra) = settings.issue.get(id) {
+            pi.power += extra.power;
+            pi.fuel += extra.fuel;
+            pi.torpedos += extra.torpedos;
+            pi.missiles += extra.missiles;
+            pi.crew += extra.crew;
+        }
+        pi
+    }).collect();
+    let humans = captains.iter().filter(|c| !c.automated()).count();
+    let hot_seat = humans > 1 && !networked;
+
+    let mut mission = settings.mission.clone();
+    prepare(&mut mission, &mut entities);
+    console::focus(None);
+    if !mission.standard() {
+        mission.brief();
+    }
+
+    let mut turn = 0;
+    let mut verdict = None;
+    let mut wrecks = Wrecks::default();
+    let mut snapshots = std::collections::VecDeque::new();
+    'game: loop {
+        turn += 1;
+        if let Some(depth) = settings.practice {
+            snapshots.push_back(Snapshot {
+                turn,
+                entities: entities.clone(),
+                players: players.clone(),
+                wrecks,
+                mission: mission.clone(),
+                rng: with_rng(|r| r.clone()),
+            });
+            if snapshots.len() > depth + 1 {
+                snapshots.pop_front();
+            }
+        }
+        for id in 0..players.len() {
+            if !players[id].alive {
+                continue;
+            }
+            if networked {
+                console::focus(None);
+                outln!("{}", tr!("waiting_for", players[id].name));
+            }
+            console::focus(Some(id));
+            if hot_seat && !captains[id].automated() {
+                hand_over(&players[id]);
+            }
+            let undoable = settings.practice.map(|_| snapshots.len().saturating_sub(1));
+            let others = sunk(&players) - players[id].ships_sunk;
+            let progress = Progress { turn, mission: &mission, others };
+            if take_turn(&mut entities, &mut players[id], captains[id].as_mut(), &progress,
+                         undoable) {
+                // Drop the snapshot of this turn, and go back to the one before.
+                snapshots.pop_back();
+                let snapshot = snapshots.pop_back().unwrap();
+                console::focus(None);
+                outln!("{}", tr!("undo.back", snapshot.turn));
+                turn = snapshot.turn - 1;
+                entities = snapshot.entities;
+                players = snapshot.players;
+                wrecks = snapshot.wrecks;
+                mission = snapshot.mission;
+                let rng = snapshot.rng;
+                with_rng(|r| *r = rng);
+                continue 'game;
+            }
+            // Player may have destroyed itself, surrendered, or won.
+            console::focus(None);
+            bury_the_dead(&mut entities, &mut players, &[]);
+            if players[id].alive {
+                console::focus(Some(id));
+                retaliation(&entities, &mut players[id], &difficulty.threat);
+                console::focus(None);
+            }
+            if players.iter().all(|pi| !pi.alive) {
+                break 'game;
+            }
+            verdict = mission.check(&situation(&entities, sunk(&players), &afloat(&players),
+                                                 turn, false));
+            if verdict.is_some() || count_all_of(&entities, EType::Ship) == 0 {
+                break 'game;
+            }
+        }
+        let mut casualties = Vec::new();
+        for (etype, cause) in move_enemies(&mut entities) {
+            match (etype, cause) {
+                (EType::Player(id), cause) => casualties.push((id, cause)),
+                (_, Cause::Mine) => wrecks.mines += 1,
+                _ => wrecks.monsters += 1,
+            }
+        }
+        // Enemies might have run into players
+        bury_the_dead(&mut entities, &mut players, &casualties);
+        if players.iter().all(|pi| !pi.alive) {
+            break;
+        }
+        // Enemies might have run into mines, and time might be up.
+        verdict = mission.check(&situation(&entities, sunk(&players), &afloat(&players),
+                                             turn, true));
+        if verdict.is_some() || count_all_of(&entities, EType::Ship) == 0 {
+            break;
+        }
+        for pi in players.iter_mut().filter(|pi| pi.alive) {
+            repair(pi, difficulty.repair);
+            let supplies = pi.supplies();
+            pi.history.push(supplies);
+        }
+    }
+    console::focus(None);
+    if let Some(Verdict::Failed(failure)) = verdict {
+        outln!("{}", mission::failure_news(failure));
+    }
+    let outcome = Outcome {
+        turns: turn,
+        ships_left: count_all_of(&entities, EType::Ship),
+        players,
+        wrecks,
+        difficulty: difficulty.clone(),
+        mission,
+        verdict,
+    };
+    for (id, captain) in captains.iter_mut().enumerate() {
+        captain.game_over(&outcome, id);
+    }
+    outcome
+}
+
+/// Tell everyone how it went.  In versus games, the captains are also
+/// ranked by ships sunk.
+pub fn report(outcome: &Outcome, versus: bool) {
+    let players = &outcome.players;
+    let survivors: Vec<_> = players.iter().filter(|pi| pi.alive).collect();
+    let ships = outcome.ships_left;
+    let won = outcome.won();
+    if won {
+        outln!("{}", tr!("report.won", names(survivors.iter().cloned())));
+    }
+    match outcome.verdict {
+        Some(Verdict::Failed(Failure::TurnLimit(_))) => {
+            outln!("{}", tr!("report.out_of_time", outcome.turns));
+        }
+        Some(Verdict::Failed(Failure::HqLost)) => outln!("{}", tr!("report.hq_lost")),
+        Some(Verdict::Failed(Failure::Detected)) => outln!("{}", tr!("report.detected")),
+        _ => (),
+    }
+    let names = names(players.iter());
+    if ships == 0 && (won || survivors.is_empty()) {
+        outln!("{}", tr!("report.got_them", names));
+        if survivors.is_empty() {
+            outln!("{}", tr!("report.posthumously"));
+        }
+    } else if won {
+        outln!("{}", tr!("report.accomplished", names));
+    } else if ships > 0 {
+        outln!("{}", tr!("report.ships_left", ships, names));
+    } else {
+        outln!("{}", tr!("report.mission_failed", names));
+    }
+    if versus {
+        let mut ranking: Vec<_> = players.iter().collect();
+        ranking.sort_by_key(|pi| std::cmp::Reverse(pi.ships_sunk));
+        outln!();
+        outln!("{}", tr!("report.ranking"));
+        for pi in &ranking {
+            outln!("    {:16} {}", pi.name, pi.ships_sunk);
+        }
+        if ranking.len() > 1 && ranking[0].ships_sunk == ranking[1].ships_sunk {
+            outln!("{}", tr!("report.tie"));
+        } else {
+            outln!("{}", tr!("report.most", ranking[0].name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_orders() {
+        let order = parse_order("nav 9 300").unwrap();
+        assert_eq!(order.command, Command::Navigate);
+        assert_eq!(order.direction, Some((1, -1)));
+        assert_eq!(order.amount, Some(300));
+
+        let order = parse_order("MISSILE 3 fuel 900 yes").unwrap();
+        assert_eq!(order.command, Command::Missile);
+        assert_eq!(order.direction, Some((1, 1)));
+        assert_eq!(order.amount, Some(900));
+        assert_eq!(order.confirm, Some(true));
+
+        let order = parse_order("dive depth 100").unwrap();
+        assert_eq!(order.command, Command::Manuever);
+        assert_eq!(order.depth, Some(100));
+
+        let order = parse_order("sab men 5").unwrap();
+        assert_eq!(order.command, Command::Sabotage);
+        assert_eq!(order.amount, Some(5));
+
+        let order = parse_order("convert power 300").unwrap();
+        assert_eq!(order.conversion, Some(Conversion::PowerToFuel));
+        assert_eq!(order.amount, Some(300));
+        assert_eq!(parse_order("conv 1").unwrap().conversion, Some(Conversion::FuelToPower));
+    }
+
+    #[test]
+    fn partial_orders() {
+        let order = parse_order("0").unwrap();
+        assert_eq!(order.command, Command::Navigate);
+        assert_eq!(order.direction, None);
+        assert_eq!(order.amount, None);
+
+        let order = parse_order("torp 6").unwrap();
+        assert_eq!(order.command, Command::Torpedo);
+        assert_eq!(order.direction, Some((1, 0)));
+
+        let order = parse_order("missile 7 600").unwrap();
+        assert_eq!(order.confirm, None);
+        assert_eq!(parse_order("log 3").unwrap().amount, Some(3));
+        assert_eq!(parse_order("st").unwrap().command, Command::Status);
+    }
+
+    #[test]
+    fn malformed_orders() {
+        let error = |input| parse_order(input).unwrap_err();
+        assert_eq!(error(""), "No orders given");
+        assert_eq!(error("fly"), "Unknown command 'fly'");
+        assert_eq!(error("12"), "There is no command 12");
+        assert!(error("s").starts_with("'s' could be"));
+        assert_eq!(error("nav 5"), "'5' is not a direction");
+        assert_eq!(error("nav 9 -1"), "'-1' is not a quantity");
+        assert_eq!(error("dive deep"), "'deep' is not a depth");
+        assert_eq!(error("torp 6 300"), "Not sure what '300' means");
+        assert_eq!(error("convert water"), "Convert fuel or power, not 'water'");
+        assert_eq!(error("sonar 3"), "Not sure what '3' means");
+    }
+
+    #[test]
+    fn ocean_sizes() {
+        let size = |arg: &str| parse_size(Some(arg.to_string())).map(|o| (o.width, o.height));
+        assert_eq!(size("30x20"), Some((30, 20)));
+        assert_eq!(size("200X10"), Some((MAX_SIZE, MIN_SIZE)));
+        assert_eq!(size("9x20"), None);
+        assert_eq!(size("20x201"), None);
+        assert_eq!(size("20"), None);
+        assert_eq!(size("20x"), None);
+        assert_eq!(size("x20"), None);
+        assert_eq!(size("20x20x20"), None);
+        assert_eq!(size("-20x20"), None);
+        assert!(parse_size(None).is_none());
+        // The size leaves wrapping as it was.
+        set_ocean(Ocean { wrap: true, ..ocean() });
+        assert!(parse_size(Some("50x50".to_string())).unwrap().wrap);
+    }
+
+    #[test]
+    fn wrap_around() {
+        let flat = Ocean { width: 20, height: 10, wrap: false };
+        let round = Ocean { wrap: true, ..flat };
+        // Across the seam, both ways, in both axes.
+        assert_eq!(flat.delta(18, 1, 20), -17);
+        assert_eq!(round.delta(18, 1, 20), 3);
+        assert_eq!(round.delta(1, 18, 20), -3);
+        assert_eq!(round.delta(9, 0, 10), 1);
+        assert_eq!(round.delta(0, 9, 10), -1);
+        assert_eq!(round.separation(18, 1, 20), 3.);
+        assert_eq!(round.separation(1, 18, 20), 3.);
+        assert_eq!(round.separation(0, 9, 10), 1.);
+        assert_eq!(flat.separation(0, 9, 10), 9.);
+        // At exactly half the ocean, either way is as short, and the
+        // direct one is taken.
+        assert_eq!(round.delta(0, 10, 20), 10);
+        assert_eq!(round.delta(10, 0, 20), -10);
+        assert_eq!(round.delta(2, 7, 10), 5);
+        assert_eq!(round.delta(7, 2, 10), -5);
+        assert_eq!(round.separation(0, 10, 20), 10.);
+        assert_eq!(round.separation(7, 2, 10), 5.);
+        // Just past half, it's shorter the other way.
+        assert_eq!(round.delta(0, 11, 20), -9);
+        assert_eq!(round.delta(0, 6, 10), -4);
+
+        assert_eq!(flat.offset(19, 9, 1, 1, 1), None);
+        assert_eq!(flat.offset(0, 5, -1, 0, 1), None);
+        assert_eq!(flat.offset(5, 5, 1, -1, 4), Some((9, 1)));
+        assert_eq!(round.offset(19, 9, 1, 1, 1), Some((0, 0)));
+        assert_eq!(round.offset(0, 0, -1, -1, 1), Some((19, 9)));
+        assert_eq!(round.offset(5, 5, 1, 0, 30), Some((15, 5)));
+        assert_eq!(round.offset(5, 5, 0, -1, 27), Some((5, 8)));
+        assert_eq!(round.offset(10, 5, 1, 1, 10), Some((0, 5)));
+    }
+}
@@ -0,0 +1,284 @@
+// The captain's log.  Each sub keeps a turn-by-turn record of what it was
+// ordered to do, what sonar saw, and what happened to it, for the log
+// command and for reviewing the game afterwards.
+
+use std::fs::File;
+use std::io::{self, Write};
+
+use debrief;
+use view::Chart;
+use super::{Cause, Command, EType, PlayerInfo, SubSystem, Supplies};
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    // An order that did something, with the stocks before and after.
+    Order { command: Command, before: Supplies, after: Supplies, sunk: u32 },
+    Contacts { ships: usize, monsters: usize, mines: usize, subs: usize },
+    Hit { severity: &'static str, power: u32, systems: Vec<SubSystem> },
+    Lost(Cause),
+}
+
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub turn: u32,
+    pub event: Event,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Logbook {
+    turn: u32,
+    pub entries: Vec<Entry>,
+}
+
+// Stock names and how much each went up or down, skipping those that
+// didn't change.
+fn changes(before: &Supplies, after: &Supplies) -> Vec<(&'static str, i64)> {
+    [("power", before.power, after.power),
+     ("fuel", before.fuel, after.fuel),
+     ("torpedos", before.torpedos, after.torpedos),
+     ("missiles", before.missiles, after.missiles),
+     ("crew", before.crew, after.crew)]
+        .iter()
+        .map(|&(name, b, a)| (name, a as i64 - b as i64))
+        .filter(|c| c.1 != 0)
+        .collect()
+}
+
+fn command_name(command: Command) -> String {
+    format!("{:?}", command).to_lowercase()
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+impl Entry {
+    fn text(&self) -> String {
+        match self.event {
+            Event::Order { command, ref before, ref after, sunk } => {
+                let mut text = command_name(command);
+                let changes: Vec<_> = changes(before, after).iter()
+                    .map(|&(name, change)| {
+                        format!("{} {:+}", tr!(&format!("stock.{}", name)), change)
+                    })
+                    .collect();
+                if !changes.is_empty() {
+                    text += &format!(": {}", changes.join(", "));
+                }
+                if sunk > 0 {
+                    text += &tr!("log.sunk", sunk);
+                }
+                text
+            }
+            Event::Contacts { ships, monsters, mines, subs } =>
+                tr!("log.contacts", ships, monsters, mines, subs),
+            Event::Hit { severity, power, ref systems } => {
+                let mut text = tr!("log.hit", tr!(&format!("severity.{}", severity)));
+                if power > 0 {
+                    text += &tr!("log.power", power);
+                }
+                if !systems.is_empty() {
+                    let systems: Vec<_> = systems.iter().map(|s| s.name()).collect();
+                    text += &tr!("log.systems", systems.join(", "));
+                }
+                text
+            }
+            Event::Lost(cause) => tr!("log.lost", debrief::cause_text(cause)),
+        }
+    }
+
+    fn json(&self) -> String {
+        let fields = match self.event {
+            Event::Order { command, ref before, ref after, sunk } => {
+                let mut fields = format!("\"event\": \"order\", \"command\": \"{}\"",
+                                         command_name(command));
+                for (name, change) in changes(before, after) {
+                    fields += &format!(", \"{}\": {}", name, change);
+                }
+                fields + &format!(", \"sunk\": {}", sunk)
+            }
+            Event::Contacts { ships, monsters, mines, subs } =>
+                format!("\"event\": \"sonar\", \"ships\": {}, \"monsters\": {}, \
+                         \"mines\": {}, \"subs\": {}", ships, monsters, mines, subs),
+            Event::Hit { severity, power, ref systems } => {
+                let systems: Vec<_> = systems.iter()
+                    .map(|s| json_string(&format!("{:?}", s)))
+                    .collect();
+                format!("\"event\": \"hit\", \"severity\": \"{}\", \"power\": {}, \
+                         \"systems\": [{}]", severity, power, systems.join(", "))
+            }
+            Event::Lost(cause) =>
+                format!("\"event\": \"lost\", \"cause\": {}",
+                        json_string(&format!("{:?}", cause))),
+        };
+        format!("{{\"turn\": {}, {}}}", self.turn, fields)
+    }
+}
+
+impl Logbook {
+    /// Entries from now on are for this turn.
+    pub fn start_turn(&mut self, turn: u32) {
+        self.turn = turn;
+    }
+
+    pub fn note(&mut self, event: Event) {
+        self.entries.push(Entry { turn: self.turn, event });
+    }
+
+    // Note event ahead of whatever came of it, which was noted from entry
+    // at on.
+    pub fn note_before(&mut self, at: usize, event: Event) {
+        self.entries.insert(at, Entry { turn: self.turn, event });
+    }
+
+    // What sonar picked up, leaving out sub own.
+    pub fn note_contacts(&mut self, chart: &Chart, own: usize) {
+        let contacts = chart.contacts();
+        let count = |wanted: &dyn Fn(&EType) -> bool| {
+            contacts.iter().filter(|c| wanted(&c.1)).count()
+        };
+        let own = EType::Player(own);
+        self.note(Event::Contacts {
+            ships: count(&|e| *e == EType::Ship),
+            monsters: count(&|e| *e == EType::Monster),
+            mines: count(&|e| *e == EType::Mine),
+            subs: count(&|e| matches!(*e, EType::Player(_)) && *e != own),
+        });
+    }
+
+    // The entries from turn since on, under a heading for each turn.
+    fn lines(&self, since: u32) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut heading = None;
+        for entry in self.entries.iter().filter(|e| e.turn >= since) {
+            if heading != Some(entry.turn) {
+                lines.push(tr!("log.turn", entry.turn));
+                heading = Some(entry.turn);
+            }
+            lines.push(format!("    {}", entry.text()));
+        }
+        lines
+    }
+
+    /// Print the log, or just the last few turns of it and this one.
+    pub fn show(&self, turns: Option<u32>) {
+        let since = turns.map_or(0, |turns| self.turn.saturating_sub(turns));
+        let lines = self.lines(since);
+        if lines.is_empty() {
+            outln!("{}", tr!("log.empty"));
+        }
+        for line in lines {
+            outln!("{}", line);
+        }
+    }
+}
+
+/// Where the log of a session's nth game goes.  Each game gets a file of its
+/// own: the first goes to path, and the rest have the game number put
+/// before the extension, as in log.json, log-2.json, log-3.json...
+pub fn game_path(path: &str, game: u32) -> String {
+    if game <= 1 {
+        return path.to_string();
+    }
+    let name = path.rfind(['/', '\\']).map_or(0, |i| i + 1);
+    match path[name..].rfind('.') {
+        Some(dot) if dot > 0 => format!("{}-{}{}", &path[..name + dot], game, &path[name + dot..]),
+        _ => format!("{}-{}", path, game),
+    }
+}
+
+/// Write the logs of these subs to path, as JSON if it ends in .json and as
+/// text otherwise.
+pub fn export(path: &str, players: &[PlayerInfo]) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    if path.to_lowercase().ends_with(".json") {
+        writeln!(file, "[")?;
+        for (i, pi) in players.iter().enumerate() {
+            writeln!(file, "  {{\"captain\": {}, \"entries\": [", json_string(&pi.name))?;
+            let entries: Vec<_> = pi.log.entries.iter()
+                .map(|e| format!("    {}", e.json()))
+                .collect();
+            writeln!(file, "{}", entries.join(",\n"))?;
+            writeln!(file, "  ]}}{}", if i + 1 < players.len() { "," } else { "" })?;
+        }
+        writeln!(file, "]")?;
+    } else {
+        for pi in players {
+            writeln!(file, "{}", tr!("log.of", pi.name))?;
+            for line in pi.log.lines(0) {
+                writeln!(file, "{}", line)?;
+            }
+            writeln!(file)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use testing::TempFile;
+
+    #[test]
+    fn json_strings() {
+        assert_eq!(json_string("Nemo"), "\"Nemo\"");
+        assert_eq!(json_string("say \"when\""), "\"say \\\"when\\\"\"");
+        assert_eq!(json_string("C:\\logs"), "\"C:\\\\logs\"");
+        assert_eq!(json_string("tab\tline\n\u{7}"), "\"tab\\u0009line\\u000a\\u0007\"");
+        assert_eq!(json_string("Capitán Núñez"), "\"Capitán Núñez\"");
+    }
+
+    #[test]
+    fn exported_json() {
+        let before = Supplies { power: 6000, fuel: 2500, torpedos: 10, missiles: 3, crew: 30 };
+        let after = Supplies { power: 5850, torpedos: 9, ..before };
+        let mut nemo = PlayerInfo::new(0, "Nemo \"the\" Captain".to_string());
+        nemo.log.start_turn(1);
+        nemo.log.note(Event::Contacts { ships: 2, monsters: 1, mines: 0, subs: 0 });
+        nemo.log.note(Event::Order { command: Command::Torpedo, before, after, sunk: 1 });
+        nemo.log.start_turn(2);
+        nemo.log.note(Event::Hit { severity: "light", power: 50,
+                                   systems: vec![SubSystem::Sonar] });
+        nemo.log.note(Event::Lost(Cause::Rammed));
+        let ahab = PlayerInfo::new(1, "Ahab".to_string());
+        let file = TempFile::new("log.json");
+        let path = file.path().to_str().unwrap();
+        export(path, &[nemo, ahab]).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(),
+                   "[\n\
+                    \x20 {\"captain\": \"Nemo \\\"the\\\" Captain\", \"entries\": [\n\
+                    \x20   {\"turn\": 1, \"event\": \"sonar\", \"ships\": 2, \"monsters\": 1, \
+                           \"mines\": 0, \"subs\": 0},\n\
+                    \x20   {\"turn\": 1, \"event\": \"order\", \"command\": \"torpedo\", \
+                           \"power\": -150, \"torpedos\": -1, \"sunk\": 1},\n\
+                    \x20   {\"turn\": 2, \"event\": \"hit\", \"severity\": \"light\", \
+                           \"power\": 50, \"systems\": [\"Sonar\"]},\n\
+                    \x20   {\"turn\": 2, \"event\": \"lost\", \"cause\": \"Rammed\"}\n\
+                    \x20 ]},\n\
+                    \x20 {\"captain\": \"Ahab\", \"entries\": [\n\
+                    \n\
+                    \x20 ]}\n\
+                    ]\n");
+    }
+
+    #[test]
+    fn a_file_for_each_game() {
+        assert_eq!(game_path("log.json", 1), "log.json");
+        assert_eq!(game_path("log.json", 2), "log-2.json");
+        assert_eq!(game_path("games/log.txt", 12), "games/log-12.txt");
+        assert_eq!(game_path("log", 3), "log-3");
+        assert_eq!(game_path("old.games/log", 2), "old.games/log-2");
+        assert_eq!(game_path(".log", 2), ".log-2");
+    }
+}
@@ -0,0 +1,351 @@
+// Missions.  A mission is a set of goals, all of which have to be met to
+// win, and of failures, any one of which ends the game in defeat.  The
+// standard mission is to sink every enemy ship, with nothing to fail.
+//
+// Missions are given on the command line as a list, e.g.
+//   sink=10,turns=30            sink 10 ships within 30 turns
+//   flagship,hq,undetected      sink the flagship, keep headquarters afloat
+//                               and keep out of sight of enemy ships
+// and checked after each sub's turn and at the end of every round.
+
+use super::Position;
+use view;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Goal {
+    // Every enemy ship sunk.
+    SinkAll,
+    // This many ships sunk by the subs.
+    Sink(u32),
+    // The enemy flagship sunk.  Where it was first seen is filled in when
+    // the game starts.
+    Flagship(Option<Position>),
+    // A sub within a cell of the waypoint, which is placed when the game
+    // starts.
+    Waypoint(Option<Position>),
+    // A sub still afloat at the end of this turn.
+    Survive(u32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Failure {
+    // The goals aren't met by the end of this turn.
+    TurnLimit(u32),
+    // Headquarters destroyed.
+    HqLost,
+    // An enemy ship spots a sub.
+    Detected,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Accomplished,
+    Failed(Failure),
+}
+
+/// How things stand, as far as the mission goes.
+pub struct Situation {
+    pub turn: u32,
+    // Every sub has had its turn, and the enemy has moved.
+    pub round_over: bool,
+    pub ships_left: u32,
+    // Ships sunk by the subs.
+    pub sunk: u32,
+    pub flagship_afloat: bool,
+    pub hq_afloat: bool,
+    // Where the subs still afloat are.
+    pub subs: Vec<Position>,
+    // An enemy ship has spotted a sub.
+    pub detected: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mission {
+    pub goals: Vec<Goal>,
+    pub failures: Vec<Failure>,
+    // A sub has been to the waypoint.
+    reached: bool,
+}
+
+impl Default for Mission {
+    fn default() -> Mission {
+        Mission { goals: vec![Goal::SinkAll], failures: Vec::new(), reached: false }
+    }
+}
+
+impl Mission {
+    /// Read a mission from a list of goals and failures, separated by
+    /// commas.  Without any goals, the goal is to sink every ship.
+    pub fn parse(text: &str) -> Result<Mission, String> {
+        let mut mission = Mission { goals: Vec::new(), ..Mission::default() };
+        for item in text.to_lowercase().split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            let (key, value) = match item.split_once('=') {
+                Some((key, value)) => (key.trim(), Some(value.trim())),
+                None => (item, None),
+            };
+            let number = || match value.and_then(|v| v.parse::<u32>().ok()) {
+                Some(n) if n > 0 => Ok(n),
+                _ => Err(tr!("mission.number", key)),
+            };
+            match key {
+                "all" => mission.goals.push(Goal::SinkAll),
+                "sink" => mission.goals.push(Goal::Sink(number()?)),
+                "flagship" => mission.goals.push(Goal::Flagship(None)),
+                "waypoint" => mission.goals.push(Goal::Waypoint(None)),
+                "survive" => mission.goals.push(Goal::Survive(number()?)),
+                "turns" => mission.failures.push(Failure::TurnLimit(number()?)),
+                "hq" => mission.failures.push(Failure::HqLost),
+                "undetected" => mission.failures.push(Failure::Detected),
+                _ => return Err(tr!("mission.unknown", item)),
+            }
+        }
+        if mission.goals.is_empty() {
+            mission.goals.push(Goal::SinkAll);
+        }
+        Ok(mission)
+    }
+
+    /// Check the mission.  None while it's still under way.
+    pub fn check(&mut self, s: &Situation) -> Option<Verdict> {
+        let reached = self.goals.iter().any(|g| match *g {
+            Goal::Waypoint(Some(ref at)) => s.subs.iter().any(|sub| sub.in_range(at, 1.)),
+            _ => false,
+        });
+        if reached {
+            self.reached = true;
+        }
+        for &failure in &self.failures {
+            let failed = match failure {
+                Failure::HqLost => !s.hq_afloat,
+                Failure::Detected => s.detected,
+                Failure::TurnLimit(_) => false,
+            };
+            if failed {
+                return Some(Verdict::Failed(failure));
+            }
+        }
+        if !s.subs.is_empty() && self.goals.iter().all(|g| self.met(g, s)) {
+            return Some(Verdict::Accomplished);
+        }
+        for &failure in &self.failures {
+            if let Failure::TurnLimit(turns) = failure {
+                if s.round_over && s.turn >= turns {
+                    return Some(Verdict::Failed(failure));
+                }
+            }
+        }
+        None
+    }
+
+    fn met(&self, goal: &Goal, s: &Situation) -> bool {
+        match *goal {
+            Goal::SinkAll => s.ships_left == 0,
+            Goal::Sink(ships) => s.sunk >= ships,
+            Goal::Flagship(_) => !s.flagship_afloat,
+            Goal::Waypoint(_) => self.reached,
+            Goal::Survive(turns) => s.turn > turns || (s.turn == turns && s.round_over),
+        }
+    }
+
+    /// Ships that still have to be sunk, if sinking them is the point.
+    pub fn ships_to_sink(&self, s: &Situation) -> Option<u32> {
+        self.goals.iter().filter_map(|g| match *g {
+            Goal::SinkAll => Some(s.ships_left),
+            Goal::Sink(ships) => Some(ships.saturating_sub(s.sunk).min(s.ships_left)),
+            _ => None,
+        }).max()
+    }
+
+    pub fn turn_limit(&self) -> Option<u32> {
+        self.failures.iter().filter_map(|f| match *f {
+            Failure::TurnLimit(turns) => Some(turns),
+            _ => None,
+        }).min()
+    }
+
+    /// Whether this is the standard mission, which goes without saying.
+    pub fn standard(&self) -> bool {
+        *self == Mission::default()
+    }
+
+    fn goal_text(goal: &Goal) -> String {
+        match *goal {
+            Goal::SinkAll => tr!("goal.sink_all"),
+            Goal::Sink(ships) => tr!("goal.sink", ships),
+            Goal::Flagship(Some(ref at)) => tr!("goal.flagship", format!("{:?}", at)),
+            Goal::Flagship(None) => tr!("goal.flagship", "?"),
+            Goal::Waypoint(Some(ref at)) => tr!("goal.waypoint", format!("{:?}", at)),
+            Goal::Waypoint(None) => tr!("goal.waypoint", "?"),
+            Goal::Survive(turns) => tr!("goal.survive", turns),
+        }
+    }
+
+    fn failure_text(failure: Failure) -> String {
+        match failure {
+            Failure::TurnLimit(turns) => tr!("failure.turn_limit", turns),
+            Failure::HqLost => tr!("failure.hq"),
+            Failure::Detected => tr!("failure.detected"),
+        }
+    }
+
+    /// The orders, read out when the game starts.
+    pub fn brief(&self) {
+        outln!("{}", tr!("mission.orders"));
+        for goal in &self.goals {
+            outln!("    {}", Mission::goal_text(goal));
+        }
+        for &failure in &self.failures {
+            outln!("    {}", Mission::failure_text(failure));
+        }
+    }
+
+    /// Each part of the mission and how it's going, for status reports
+    /// from the sub at own.
+    pub fn progress(&self, s: &Situation, own: &Position) {
+        outln!("{}", tr!("mission.orders"));
+        for goal in &self.goals {
+            let progress = if self.met(goal, s) {
+                tr!("progress.done")
+            } else {
+                match *goal {
+                    Goal::SinkAll => tr!("progress.to_go", s.ships_left),
+                    Goal::Sink(ships) => tr!("progress.to_go", ships.saturating_sub(s.sunk)),
+                    Goal::Flagship(_) => tr!("progress.afloat"),
+                    Goal::Waypoint(Some(ref at)) => view::bearing(own, at),
+                    Goal::Waypoint(None) => String::new(),
+                    Goal::Survive(_) => tr!("progress.turn", s.turn),
+                }
+            };
+            outln!("    {}: {}", Mission::goal_text(goal), progress);
+        }
+        for &failure in &self.failures {
+            let progress = match failure {
+                Failure::TurnLimit(_) => tr!("progress.turn", s.turn),
+                _ => tr!("progress.holding"),
+            };
+            outln!("    {}: {}", Mission::failure_text(failure), progress);
+        }
+    }
+}
+
+/// What ended the mission in failure, for the news and the final report.
+pub fn failure_news(failure: Failure) -> String {
+    match failure {
+        Failure::TurnLimit(_) => tr!("out_of_time"),
+        Failure::HqLost => tr!("news.hq_lost"),
+        Failure::Detected => tr!("news.detected"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The middle of the first turn of a standard game, with one sub afloat.
+    fn situation() -> Situation {
+        Situation {
+            turn: 1,
+            round_over: false,
+            ships_left: 20,
+            sunk: 0,
+            flagship_afloat: true,
+            hq_afloat: true,
+            subs: vec![Position { x: 10, y: 10 }],
+            detected: false,
+        }
+    }
+
+    #[test]
+    fn parse_missions() {
+        assert_eq!(Mission::parse("").unwrap(), Mission::default());
+        assert!(Mission::parse("all").unwrap().standard());
+        let mission = Mission::parse("Sink=10, turns=30,hq").unwrap();
+        assert_eq!(mission.goals, [Goal::Sink(10)]);
+        assert_eq!(mission.failures, [Failure::TurnLimit(30), Failure::HqLost]);
+        assert_eq!(mission.turn_limit(), Some(30));
+        // Failures alone leave the standard goal.
+        let mission = Mission::parse("undetected,turns=40,turns=25").unwrap();
+        assert_eq!(mission.goals, [Goal::SinkAll]);
+        assert_eq!(mission.turn_limit(), Some(25));
+        let mission = Mission::parse("flagship,waypoint,survive=12").unwrap();
+        assert_eq!(mission.goals, [Goal::Flagship(None), Goal::Waypoint(None), Goal::Survive(12)]);
+    }
+
+    #[test]
+    fn bad_missions() {
+        assert!(Mission::parse("sink").is_err());
+        assert!(Mission::parse("sink=0").is_err());
+        assert!(Mission::parse("sink=-3").is_err());
+        assert!(Mission::parse("turns=soon").is_err());
+        assert!(Mission::parse("survive=").is_err());
+        assert!(Mission::parse("all,escort").is_err());
+    }
+
+    #[test]
+    fn sink_quota_within_turn_limit() {
+        let mut mission = Mission::parse("sink=5,turns=10").unwrap();
+        let mut s = situation();
+        s.sunk = 4;
+        assert_eq!(mission.check(&s), None);
+        // Turn 10 is still in play until the round is over.
+        s.turn = 10;
+        assert_eq!(mission.check(&s), None);
+        s.sunk = 5;
+        assert_eq!(mission.check(&s), Some(Verdict::Accomplished));
+        // Meeting the quota as the last round ends still counts.
+        s.round_over = true;
+        assert_eq!(mission.check(&s), Some(Verdict::Accomplished));
+        s.sunk = 4;
+        assert_eq!(mission.check(&s), Some(Verdict::Failed(Failure::TurnLimit(10))));
+        assert_eq!(mission.ships_to_sink(&s), Some(1));
+    }
+
+    #[test]
+    fn every_goal_has_to_be_met() {
+        let mut mission = Mission::parse("sink=3,flagship,survive=5").unwrap();
+        let mut s = situation();
+        s.sunk = 3;
+        s.flagship_afloat = false;
+        assert_eq!(mission.check(&s), None);
+        s.turn = 5;
+        assert_eq!(mission.check(&s), None);
+        s.round_over = true;
+        assert_eq!(mission.check(&s), Some(Verdict::Accomplished));
+        // Goals are only met by subs that are still afloat.
+        s.subs.clear();
+        assert_eq!(mission.check(&s), None);
+    }
+
+    #[test]
+    fn failures_come_first() {
+        // Headquarters lost on the same turn the last ship goes down.
+        let mut mission = Mission::parse("all,hq").unwrap();
+        let mut s = situation();
+        s.ships_left = 0;
+        s.sunk = 20;
+        s.hq_afloat = false;
+        assert_eq!(mission.check(&s), Some(Verdict::Failed(Failure::HqLost)));
+        s.hq_afloat = true;
+        assert_eq!(mission.check(&s), Some(Verdict::Accomplished));
+
+        let mut mission = Mission::parse("sink=1,undetected").unwrap();
+        s.detected = true;
+        assert_eq!(mission.check(&s), Some(Verdict::Failed(Failure::Detected)));
+        // Without the failure, being seen doesn't matter.
+        assert_eq!(Mission::parse("sink=1").unwrap().check(&s), Some(Verdict::Accomplished));
+    }
+
+    #[test]
+    fn waypoint_stays_reached() {
+        let mut mission = Mission::parse("waypoint,sink=2").unwrap();
+        mission.goals[0] = Goal::Waypoint(Some(Position { x: 5, y: 5 }));
+        let mut s = situation();
+        assert_eq!(mission.check(&s), None);
+        s.subs = vec![Position { x: 6, y: 4 }];
+        assert_eq!(mission.check(&s), None);
+        // Leaving the waypoint once it's been reached is fine.
+        s.subs = vec![Position { x: 15, y: 15 }];
+        s.sunk = 2;
+        assert_eq!(mission.check(&s), Some(Verdict::Accomplished));
+    }
+}
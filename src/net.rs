@@ -0,0 +1,148 @@
+// Networked play.  The host runs the game; every other captain joins with
+// a thin client that prints what it's sent and answers prompts.
+//
+// The wire protocol is line based.  From the host:
+//   O<text>   print text, with \, newline and return escaped
+//   A         read a line from the captain and send it back
+// The client sends nothing but answers.
+
+use std::io::{stdin, stdout, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process;
+
+use console::Console;
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('r') => result.push('\r'),
+                Some(other) => result.push(other),
+                None => {}
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+// A captain at the far end of a connection.
+pub struct Remote {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Remote {
+    fn new(stream: TcpStream) -> Remote {
+        stream.set_nodelay(true).ok();
+        Remote {
+            reader: BufReader::new(stream.try_clone().expect("Failed to clone stream")),
+            writer: stream,
+        }
+    }
+
+    fn lost(&self) -> ! {
+        println!("{}", tr!("net.lost", format!("{:?}", self.writer.peer_addr().ok())));
+        process::exit(1);
+    }
+}
+
+impl Console for Remote {
+    fn write(&mut self, text: &str) {
+        if writeln!(self.writer, "O{}", escape(text)).is_err() {
+            self.lost();
+        }
+    }
+
+    fn read_line(&mut self) -> Option<String> {
+        let mut result = String::new();
+        if writeln!(self.writer, "A").is_err() {
+            self.lost();
+        }
+        match self.reader.read_line(&mut result) {
+            Ok(0) | Err(_) => self.lost(),
+            Ok(_) => Some(result),
+        }
+    }
+}
+
+// Wait for the other captains to join.  The host's own terminal is the
+// first console.
+pub fn host(port: u16, players: usize) -> Vec<Box<dyn Console>> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).unwrap_or_else(|e| {
+        println!("{}", tr!("net.listen", port, e));
+        process::exit(1);
+    });
+    let mut consoles: Vec<Box<dyn Console>> = vec![Box::new(::console::Terminal)];
+    while consoles.len() < players {
+        println!("{}", tr!("net.waiting", consoles.len() + 1, port));
+        match listener.accept() {
+            Ok((stream, addr)) => {
+                println!("{}", tr!("net.joined", consoles.len() + 1, addr));
+                consoles.push(Box::new(Remote::new(stream)));
+            }
+            Err(e) => println!("{}", tr!("net.failed", e)),
+        }
+    }
+    consoles
+}
+
+// Play a game hosted elsewhere.
+pub fn join(addr: &str) {
+    let stream = TcpStream::connect(addr).unwrap_or_else(|e| {
+        println!("{}", tr!("net.unreachable", addr, e));
+        process::exit(1);
+    });
+    println!("{}", tr!("net.connected", addr));
+    let mut writer = stream.try_clone().expect("Failed to clone stream");
+    for line in BufReader::new(stream).lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        if let Some(text) = line.strip_prefix('O') {
+            print!("{}", unescape(text));
+            stdout().flush().unwrap();
+        } else if line == "A" {
+            let mut answer = String::new();
+            if stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+                break;
+            }
+            if !answer.ends_with('\n') {
+                answer.push('\n');
+            }
+            if write!(writer, "{}", answer).is_err() {
+                break;
+            }
+        }
+    }
+    println!();
+    println!("{}", tr!("net.closed"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        let texts = ["", "plain", "two\nlines", "\\n is not a newline", "..1..\r..2..\r", "\\"];
+        for text in texts.iter() {
+            let escaped = escape(text);
+            assert!(!escaped.contains('\n') && !escaped.contains('\r'), "{:?}", escaped);
+            assert_eq!(unescape(&escaped), *text);
+        }
+        assert_eq!(escape("a\\b\nc"), "a\\\\b\\nc");
+        // A stray backslash at the end of a line is dropped.
+        assert_eq!(unescape("ends with \\"), "ends with ");
+        assert_eq!(unescape("\\q"), "q");
+    }
+}
@@ -0,0 +1,368 @@
+// The rules of the game.  The figures the game is balanced by live here, and
+// a rules file can change any of them, so house rules can be tried out
+// without rebuilding.  A rules file has a rule per line:
+//
+//   # Heavier torpedos
+//   torpedo_power = 200
+//   torpedo_range = 9
+//
+// Blank lines and lines starting with # are skipped, and rules that aren't
+// mentioned keep their standard values.  The help pages quote the rules in
+// play, so they stay in step.  What each rule is for is written beside it
+// in English; other languages translate it in the catalog, as rule.NAME.
+//
+// What depth charges do at each level of threat is here, but the threat
+// levels themselves belong to the difficulty (see difficulty.rs).
+
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+
+use text;
+
+// Declares the rules, their standard values, and what each is for, and
+// lets them be read and written by name.
+macro_rules! rules {
+    ($(#[doc = $doc:literal] $name:ident: $t:ty = $value:expr,)*) => {
+        /// Everything the game plays by.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct Rules {
+            $(#[doc = $doc] pub $name: $t,)*
+        }
+
+        /// The rules as they've always been.
+        pub const STANDARD: Rules = Rules { $($name: $value,)* };
+
+        impl Rules {
+            fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                match name {
+                    $(stringify!($name) => {
+                        self.$name = value.parse()
+                            .map_err(|_| tr!("rules.value", name, value))?;
+                    })*
+                    _ => return Err(tr!("rules.unknown", name)),
+                }
+                Ok(())
+            }
+
+            // Each rule's name, value, and what it's for.
+            fn entries(&self) -> Vec<(&'static str, String, &'static str)> {
+                vec![$((stringify!($name), self.$name.to_string(), $doc.trim()),)*]
+            }
+        }
+    };
+}
+
+rules! {
+    /// Crew needed aboard to navigate.
+    navigate_crew: u32 = 9,
+    /// Crew needed aboard to use the sonar.
+    sonar_crew: u32 = 6,
+    /// Crew needed aboard to fire a torpedo.
+    torpedo_crew: u32 = 10,
+    /// Crew needed aboard to fire a missile.
+    missile_crew: u32 = 24,
+    /// Crew needed aboard to dive or rise.
+    manuever_crew: u32 = 13,
+    /// Crew needed aboard to make a status report.
+    status_crew: u32 = 4,
+    /// Crew needed aboard to send out saboteurs.
+    sabotage_crew: u32 = 11,
+    /// Crew needed aboard to convert fuel or power.
+    convert_crew: u32 = 6,
+    /// Men who have to stay aboard while the others are out sabotaging.
+    skeleton_crew: u32 = 10,
+    /// Power used to move one cell.
+    power_per_move: u32 = 100,
+    /// Power used by a sonar ping.
+    sonar_power: u32 = 50,
+    /// Power used to fire a torpedo.
+    torpedo_power: u32 = 150,
+    /// Feet dived or risen for one unit of power.
+    feet_per_power: u32 = 2,
+    /// Pounds of fuel to carry a missile one cell.
+    fuel_per_cell: f32 = 75.,
+    /// Pounds of fuel made from a unit of power, or needed for one.
+    fuel_per_power: u32 = 3,
+    /// Pushing more power than this through the pile may send it supercritical.
+    safe_power: u32 = 1000,
+    /// Chance the pile holds when pushed past safe_power.
+    pile_safety: f32 = 0.43,
+    /// At this depth or shallower, the sub is slow and can dock with headquarters.
+    shallow_depth: i32 = 50,
+    /// At this depth or deeper, torpedos and missiles are risky.
+    deep_depth: i32 = 2000,
+    /// The hull gives way at this depth.
+    crush_depth: i32 = 3000,
+    /// Furthest a torpedo runs.
+    torpedo_range: i32 = 7,
+    /// Most a torpedo falls short of its range, at random.
+    torpedo_spread: i32 = 4,
+    /// Extra range for torpedos fired below shallow_depth.
+    deep_torpedo_bonus: i32 = 5,
+    /// Chance a torpedo fired at deep_depth or deeper doesn't implode the sub.
+    deep_torpedo_safety: f32 = 0.5,
+    /// Chance a missile launched at deep_depth or deeper doesn't blow up the sub.
+    deep_missile_safety: f32 = 0.5,
+    /// How close to headquarters a sub has to be to dock.
+    docking_range: f32 = 2.,
+    /// What a resupply brings power up to.
+    resupply_power: u32 = 4000,
+    /// What a resupply brings torpedos up to.
+    resupply_torpedos: u32 = 8,
+    /// What a resupply brings missiles up to.
+    resupply_missiles: u32 = 2,
+    /// What a resupply brings fuel up to.
+    resupply_fuel: u32 = 1500,
+    /// What a resupply brings the crew up to.
+    resupply_crew: u32 = 25,
+    /// How close saboteurs have to get to a ship.
+    sabotage_range: f32 = 2.,
+    /// Chance each saboteur comes back.
+    saboteur_survival: f32 = 0.6,
+    /// Chance a sea monster nearby eats each saboteur on the way back.
+    saboteur_eaten: f32 = 0.15,
+    /// How close a sea monster has to be to eat a passing sub.
+    monster_reach: f32 = 2.,
+    /// Chance a sea monster in reach eats the sub.
+    monster_bite: f32 = 0.25,
+    /// Chance the sub slips past a sea monster it runs into.
+    monster_escape: f32 = 0.21,
+    /// Chance an enemy ship running into a mine steers clear of it.
+    ship_mine_avoidance: f32 = 0.7,
+    /// How close enemy ships have to be to drop depth charges.
+    ship_range: f32 = 4.,
+    /// Chance depth charges within the first threat level do no harm.
+    charge_miss: f32 = 0.92,
+    /// Chance depth charges within the second threat level do only light damage.
+    light_hit_chance: f32 = 0.96,
+    /// Power lost to a light hit.
+    light_hit_power: u32 = 50,
+    /// Most extra power lost to a light hit, at random.
+    light_hit_spread: u32 = 0,
+    /// Systems a light hit damages.
+    light_hit_systems: u32 = 1,
+    /// Most damage a light hit does to each system.
+    light_hit_damage: f32 = 2.,
+    /// Chance depth charges within the third threat level do only moderate damage.
+    moderate_hit_chance: f32 = 0.975,
+    /// Power lost to a moderate hit.
+    moderate_hit_power: u32 = 75,
+    /// Most extra power lost to a moderate hit, at random.
+    moderate_hit_spread: u32 = 30,
+    /// Systems a moderate hit damages.
+    moderate_hit_systems: u32 = 2,
+    /// Most damage a moderate hit does to each system.
+    moderate_hit_damage: f32 = 8.,
+    /// Chance depth charges within the fourth threat level do only heavy damage.
+    heavy_hit_chance: f32 = 0.983,
+    /// Power lost to a heavy hit.
+    heavy_hit_power: u32 = 200,
+    /// Most extra power lost to a heavy hit, at random.
+    heavy_hit_spread: u32 = 76,
+    /// Systems a heavy hit damages.
+    heavy_hit_systems: u32 = 4,
+    /// Most damage a heavy hit does to each system.
+    heavy_hit_damage: f32 = 9.,
+    /// Power lost to a critical hit.
+    critical_hit_power: u32 = 200,
+    /// Most extra power lost to a critical hit, at random.
+    critical_hit_spread: u32 = 76,
+    /// Systems a critical hit damages.
+    critical_hit_systems: u32 = 4,
+    /// Most damage a critical hit does to each system.
+    critical_hit_damage: f32 = 11.,
+    /// Chance two sea monsters that run into each other both swim on.
+    monster_standoff: f32 = 0.8,
+    /// Play as the original BASIC game did, where this one differs.
+    classic: bool = false,
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        STANDARD
+    }
+}
+
+impl Rules {
+    /// Standard rules with the changes in the file at path.
+    pub fn load(path: &Path) -> Result<Rules, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut rules = STANDARD;
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let result = match line.split_once('=') {
+                Some((name, value)) => rules.set(name.trim(), value.trim()),
+                None => Err(tr!("rules.expected", line)),
+            };
+            result.map_err(|e| tr!("rules.line", path.display(), n + 1, e))?;
+        }
+        rules.validate().map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(rules)
+    }
+
+    /// Whether the rules make a game that can be played.
+    pub fn validate(&self) -> Result<(), String> {
+        let chances = [("pile_safety", self.pile_safety),
+                       ("deep_torpedo_safety", self.deep_torpedo_safety),
+                       ("deep_missile_safety", self.deep_missile_safety),
+                       ("saboteur_survival", self.saboteur_survival),
+                       ("saboteur_eaten", self.saboteur_eaten),
+                       ("monster_bite", self.monster_bite),
+                       ("monster_escape", self.monster_escape),
+                       ("ship_mine_avoidance", self.ship_mine_avoidance),
+                       ("charge_miss", self.charge_miss),
+                       ("light_hit_chance", self.light_hit_chance),
+                       ("moderate_hit_chance", self.moderate_hit_chance),
+                       ("heavy_hit_chance", self.heavy_hit_chance),
+                       ("monster_standoff", self.monster_standoff)];
+        for &(name, chance) in chances.iter() {
+            if !(0. ..=1.).contains(&chance) {
+                return Err(tr!("rules.chance", name));
+            }
+        }
+        let ranges = [("fuel_per_cell", self.fuel_per_cell),
+                      ("docking_range", self.docking_range),
+                      ("sabotage_range", self.sabotage_range),
+                      ("monster_reach", self.monster_reach),
+                      ("ship_range", self.ship_range),
+                      ("light_hit_damage", self.light_hit_damage),
+                      ("moderate_hit_damage", self.moderate_hit_damage),
+                      ("heavy_hit_damage", self.heavy_hit_damage),
+                      ("critical_hit_damage", self.critical_hit_damage)];
+        for &(name, range) in ranges.iter() {
+            if !range.is_finite() || range < 0. {
+                return Err(tr!("rules.negative", name));
+            }
+        }
+        if self.fuel_per_cell == 0. {
+            return Err(tr!("rules.above_zero", "fuel_per_cell"));
+        }
+        let divisors = [("power_per_move", self.power_per_move),
+                        ("feet_per_power", self.feet_per_power),
+                        ("fuel_per_power", self.fuel_per_power)];
+        for &(name, divisor) in divisors.iter() {
+            if divisor == 0 {
+                return Err(tr!("rules.above_zero", name));
+            }
+        }
+        if !(0 < self.shallow_depth && self.shallow_depth < self.deep_depth &&
+             self.deep_depth < self.crush_depth) {
+            return Err(tr!("rules.depths"));
+        }
+        if self.sabotage_crew <= self.skeleton_crew {
+            return Err(tr!("rules.sabotage"));
+        }
+        if self.torpedo_spread < 0 || self.deep_torpedo_bonus < 0 ||
+            self.torpedo_range <= self.torpedo_spread {
+            return Err(tr!("rules.torpedos"));
+        }
+        Ok(())
+    }
+
+    /// The rules as a rules file, with what each is for.
+    pub fn to_text(&self) -> String {
+        self.entries().iter()
+            .map(|&(name, ref value, about)| {
+                let about = text::translation(&format!("rule.{}", name)).unwrap_or(about);
+                format!("# {}\n{} = {}\n", about, name, value)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The rules that differ from the standard ones, as NAME = VALUE.
+    pub fn changes(&self) -> Vec<String> {
+        self.entries().iter().zip(STANDARD.entries().iter())
+            .filter(|(rule, standard)| rule.1 != standard.1)
+            .map(|(rule, _)| format!("{} = {}", rule.0, rule.1))
+            .collect()
+    }
+}
+
+thread_local!(static RULES: Cell<Rules> = const { Cell::new(STANDARD) });
+
+/// The rules in play.
+pub fn rules() -> Rules {
+    RULES.with(|r| r.get())
+}
+
+pub fn set_rules(rules: Rules) {
+    RULES.with(|r| r.set(rules));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testing::TempFile;
+
+    fn load(name: &str, text: &str) -> Result<Rules, String> {
+        Rules::load(TempFile::with(&format!("{}.rules", name), text).path())
+    }
+
+    #[test]
+    fn house_rules() {
+        let rules = load("house", "# Heavier torpedos\n\
+                                   torpedo_power = 200\n\
+                                   \n\
+                                   \x20 torpedo_range=9  \n\
+                                   classic = true\n").unwrap();
+        assert_eq!(rules.torpedo_power, 200);
+        assert_eq!(rules.torpedo_range, 9);
+        assert!(rules.classic);
+        assert_eq!(rules.sonar_power, STANDARD.sonar_power);
+        assert_eq!(rules.changes(), ["torpedo_power = 200", "torpedo_range = 9", "classic = true"]);
+        assert_eq!(load("empty", "").unwrap(), STANDARD);
+    }
+
+    #[test]
+    fn rules_file_round_trip() {
+        let mut rules = STANDARD;
+        rules.monster_bite = 0.5;
+        rules.crush_depth = 4000;
+        rules.critical_hit_damage = 20.;
+        assert_eq!(load("round-trip", &rules.to_text()).unwrap(), rules);
+    }
+
+    #[test]
+    fn malformed_rules_files() {
+        let error = load("no-equals", "torpedo_power 200").unwrap_err();
+        assert!(error.ends_with("line 1: expected NAME = VALUE, not 'torpedo_power 200'"),
+                "{}", error);
+        let error = load("unknown", "# Fine\n\ntorpedo_speed = 3").unwrap_err();
+        assert!(error.ends_with("line 3: there's no rule called 'torpedo_speed'"), "{}", error);
+        let error = load("not-a-number", "sonar_power = lots").unwrap_err();
+        assert!(error.ends_with("line 1: sonar_power can't be 'lots'"), "{}", error);
+        assert!(load("negative", "sonar_power = -50").is_err());
+        assert!(Rules::load(Path::new("/nonexistent/seawar.rules")).is_err());
+    }
+
+    #[test]
+    fn unplayable_rules() {
+        let error = |text| load("unplayable", text).unwrap_err();
+        assert!(error("monster_bite = 1.5").ends_with("monster_bite is a chance, from 0 to 1"));
+        assert!(error("ship_mine_avoidance = -0.1").contains("ship_mine_avoidance is a chance"));
+        assert!(error("ship_range = -1").ends_with("ship_range can't be below 0"));
+        assert!(error("fuel_per_cell = 0").ends_with("fuel_per_cell has to be above 0"));
+        assert!(error("power_per_move = 0").ends_with("power_per_move has to be above 0"));
+        assert!(error("deep_depth = 3000").contains("depths have to go"));
+        assert!(error("shallow_depth = 0").contains("depths have to go"));
+        assert!(error("torpedo_spread = 7").contains("torpedos have to run at least a cell"));
+        assert!(error("heavy_hit_chance = 2").ends_with("heavy_hit_chance is a chance, from 0 to 1"));
+        assert!(error("light_hit_damage = -1").ends_with("light_hit_damage can't be below 0"));
+    }
+
+    #[test]
+    fn saboteurs_need_men_to_spare() {
+        let error = load("crew", "sabotage_crew = 8").unwrap_err();
+        assert!(error.ends_with("skeleton_crew < sabotage_crew"), "{}", error);
+        assert!(load("equal-crew", "sabotage_crew = 10").is_err());
+        assert!(load("crew-order", "skeleton_crew = 5\nsabotage_crew = 6").is_ok());
+        let rules = Rules { skeleton_crew: 11, ..STANDARD };
+        assert!(rules.validate().is_err());
+        assert!(STANDARD.validate().is_ok());
+    }
+}
@@ -0,0 +1,188 @@
+// Scoring, and the table of high scores kept between games.
+//
+// The table lives in a plain text file, one captain per line: best score,
+// games played and name, separated by tabs.  Only each captain's best
+// score is kept.
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use super::{Cause, Outcome};
+
+// Points for each ship sunk.
+const SHIP_POINTS: i64 = 100;
+// Clearing the ocean, with a bonus for every turn under SPEEDY_TURNS.
+const VICTORY_POINTS: i64 = 1000;
+const SPEEDY_TURNS: u32 = 100;
+const TURN_POINTS: i64 = 10;
+const CREW_POINTS: i64 = 10;
+const HQ_PENALTY: i64 = -500;
+const SURRENDER_PENALTY: i64 = -250;
+
+// Captains shown in the table.
+const TABLE_SIZE: usize = 10;
+
+/// A score, item by item.  Items are named by message key.
+#[derive(Clone, Debug)]
+pub struct Score {
+    pub items: Vec<(&'static str, i64)>,
+}
+
+impl Score {
+    pub fn total(&self) -> i64 {
+        self.items.iter().map(|i| i.1).sum()
+    }
+}
+
+/// How sub id scored.  Ships sunk count for everybody, clearing the ocean
+/// quickly for the survivors, and what's left aboard only if the sub came
+/// home.
+pub fn score(outcome: &Outcome, id: usize) -> Score {
+    let pi = &outcome.players[id];
+    let mut items = vec![("score.ships_sunk", pi.ships_sunk as i64 * SHIP_POINTS)];
+    if outcome.won() && pi.alive {
+        items.push(("score.victory", VICTORY_POINTS));
+        items.push(("score.speed", SPEEDY_TURNS.saturating_sub(outcome.turns) as i64 * TURN_POINTS));
+    }
+    if pi.alive {
+        items.push(("score.crew", pi.crew as i64 * CREW_POINTS));
+        items.push(("score.supplies", (pi.power / 100 + pi.fuel / 100) as i64 +
+                    pi.torpedos as i64 * 10 + pi.missiles as i64 * 25));
+    }
+    if pi.record.hq_destroyed {
+        items.push(("score.hq_lost", HQ_PENALTY));
+    }
+    if pi.cause == Some(Cause::Surrendered) {
+        items.push(("score.surrender", SURRENDER_PENALTY));
+    }
+    // Harder games are worth more, easier ones less.
+    let subtotal: i64 = items.iter().map(|i| i.1).sum();
+    let reward = outcome.difficulty.reward;
+    if reward != 1. && subtotal > 0 {
+        items.push(("score.difficulty", (subtotal as f32 * (reward - 1.)).round() as i64));
+    }
+    Score { items }
+}
+
+/// Print the score, with how it was made up.
+pub fn show_score(name: &str, score: &Score) {
+    outln!("{}", tr!("score.total", name, score.total()));
+    for &(item, points) in score.items.iter().filter(|i| i.1 != 0) {
+        outln!("    {:18} {:+}", tr!(item), points);
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Best {
+    name: String,
+    score: i64,
+    games: u32,
+}
+
+pub struct HighScores {
+    table: Vec<Best>,
+}
+
+/// Where the table is kept unless told otherwise.
+pub fn default_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".seawar_scores"))
+}
+
+impl HighScores {
+    /// The table in the file at path.  A missing file is an empty table,
+    /// and lines that can't be read are dropped.
+    pub fn load(path: &PathBuf) -> HighScores {
+        let text = fs::read_to_string(path).unwrap_or_default();
+        let table = text.lines().filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let score = fields.next()?.parse().ok()?;
+            let games = fields.next()?.parse().ok()?;
+            let name = fields.next()?.to_string();
+            Some(Best { name, score, games })
+        }).collect();
+        HighScores { table }
+    }
+
+    pub fn save(&self, path: &PathBuf) -> io::Result<()> {
+        let mut file = fs::File::create(path)?;
+        for best in &self.table {
+            writeln!(file, "{}\t{}\t{}", best.score, best.games, best.name)?;
+        }
+        Ok(())
+    }
+
+    /// Count a game for name.  Returns true if it's their best yet.
+    pub fn add(&mut self, name: &str, score: i64) -> bool {
+        let name = name.replace(['\t', '\n'], " ");
+        let personal_best = match self.table.iter_mut().find(|b| b.name == name) {
+            Some(best) => {
+                best.games += 1;
+                let better = score > best.score;
+                if better {
+                    best.score = score;
+                }
+                better
+            }
+            None => {
+                self.table.push(Best { name, score, games: 1 });
+                true
+            }
+        };
+        self.table.sort_by_key(|b| -b.score);
+        personal_best
+    }
+
+    /// Print the top of the table, marking the captains in the last game.
+    pub fn show(&self, playing: &[&str]) {
+        outln!("{}", tr!("score.table"));
+        for (rank, best) in self.table.iter().take(TABLE_SIZE).enumerate() {
+            let mark = if playing.contains(&best.name.as_str()) { "*" } else { " " };
+            outln!("  {} {:4}  {:16} {:7}  {:5}",
+                   mark, rank + 1, best.name, best.score, best.games);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testing::TempFile;
+
+    fn names(scores: &HighScores) -> Vec<(&str, i64, u32)> {
+        scores.table.iter().map(|b| (b.name.as_str(), b.score, b.games)).collect()
+    }
+
+    #[test]
+    fn personal_bests() {
+        let mut scores = HighScores::load(TempFile::new("missing.scores").path());
+        assert!(scores.table.is_empty());
+        assert!(scores.add("Nemo", 500));
+        assert!(scores.add("Ahab", 900));
+        assert!(!scores.add("Nemo", 300));
+        assert!(scores.add("Nemo", 1200));
+        assert!(scores.add("Tab\tand\nnewline", -50));
+        assert_eq!(names(&scores), [("Nemo", 1200, 3), ("Ahab", 900, 1),
+                                    ("Tab and newline", -50, 1)]);
+    }
+
+    #[test]
+    fn scores_file_round_trip() {
+        let file = TempFile::new("round-trip.scores");
+        let mut scores = HighScores::load(file.path());
+        scores.add("Nemo", 500);
+        scores.add("Captain Ahab", -250);
+        scores.save(file.path()).unwrap();
+        let loaded = HighScores::load(file.path());
+        assert_eq!(names(&loaded), names(&scores));
+    }
+
+    #[test]
+    fn unreadable_lines_are_dropped() {
+        let file = TempFile::with("damaged.scores",
+                                  "700\t2\tNemo\nlots\t1\tAhab\n300\tDahl\n\n-20\t4\tName\twith tab\n");
+        let scores = HighScores::load(file.path());
+        assert_eq!(names(&scores), [("Nemo", 700, 2), ("Name\twith tab", -20, 4)]);
+    }
+}
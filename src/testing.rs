@@ -0,0 +1,33 @@
+// Helpers shared by the unit tests.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+/// A file in the temp directory, named so tests running at the same time
+/// don't trip over each other, and removed once done with.
+pub struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Nothing is written: name, such as "log.json", may not exist.
+    pub fn new(name: &str) -> TempFile {
+        TempFile(env::temp_dir().join(format!("seawar-{}-{}", process::id(), name)))
+    }
+
+    pub fn with(name: &str, text: &str) -> TempFile {
+        let file = TempFile::new(name);
+        fs::write(&file.0, text).unwrap();
+        file
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        fs::remove_file(&self.0).ok();
+    }
+}
@@ -0,0 +1,1462 @@
+// What the game says, in each language it speaks.
+//
+// Messages are looked up by key, and {0}, {1}... in the text are replaced
+// by the arguments, so translations can put them in whatever order reads
+// best.  A message missing from a language falls back to English.
+
+use std::cell::Cell;
+use std::env;
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+impl Language {
+    /// From a code like "es", or a locale like "es_ES.UTF-8".
+    pub fn from_code(code: &str) -> Option<Language> {
+        let code = code.split(['_', '.', '-']).next().unwrap_or("");
+        match code.to_lowercase().as_str() {
+            "en" => Some(Language::English),
+            "es" => Some(Language::Spanish),
+            _ => None,
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => &ENGLISH,
+            Language::Spanish => &SPANISH,
+        }
+    }
+}
+
+thread_local!(static LANGUAGE: Cell<Language> = const { Cell::new(Language::English) });
+
+pub fn set_language(language: Language) {
+    LANGUAGE.with(|l| l.set(language));
+}
+
+/// The language LANG asks for, if it's one we have.
+pub fn from_environment() -> Option<Language> {
+    env::var("LANG").ok().and_then(|lang| Language::from_code(&lang))
+}
+
+fn lookup(key: &str) -> &str {
+    let language = LANGUAGE.with(|l| l.get());
+    search(&[language.catalog(), Language::English.catalog()], key).unwrap_or(key)
+}
+
+/// The message for key from the first of the catalogs that has it.
+fn search(catalogs: &[&'static [(&'static str, &'static str)]], key: &str) -> Option<&'static str> {
+    catalogs.iter()
+        .filter_map(|c| c.iter().find(|m| m.0 == key))
+        .map(|m| m.1)
+        .next()
+}
+
+/// The message for key in the language in play, if that language has one.
+/// For text kept in English beside the code, such as what each rule is for.
+pub fn translation(key: &str) -> Option<&'static str> {
+    let language = LANGUAGE.with(|l| l.get());
+    search(&[language.catalog()], key)
+}
+
+/// The message for key, with the arguments filled in.  The message is read
+/// once, so an argument that happens to contain {1} is left as it is.
+pub fn format(key: &str, args: &[&dyn Display]) -> String {
+    let mut text = String::new();
+    let mut rest = lookup(key);
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        rest = &rest[open..];
+        let arg = rest.find('}')
+            .and_then(|close| rest[1..close].parse::<usize>().ok().map(|i| (i, close)))
+            .and_then(|(i, close)| args.get(i).map(|arg| (arg, close)));
+        match arg {
+            Some((arg, close)) => {
+                text.push_str(&arg.to_string());
+                rest = &rest[close + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => ($crate::text::format($key, &[]));
+    ($key:expr, $($arg:expr),+) => ($crate::text::format($key, &[$(&$arg),+]));
+}
+
+const ENGLISH: [(&str, &str); 436] = [
+    ("menu",
+     "The Commands are:\n\
+      \x20     0: Navigate       navigate DIRECTION POWER\n\
+      \x20     1: Sonar          sonar\n\
+      \x20     2: Fire Torpedo   torpedo DIRECTION\n\
+      \x20     3: Fire Missile   missile DIRECTION FUEL\n\
+      \x20     4: Manuever       dive DEPTH\n\
+      \x20     5: Status         status\n\
+      \x20     6: Resupply       resupply\n\
+      \x20     7: Sabotage       sabotage MEN\n\
+      \x20     8: Convert Power  convert fuel|power AMOUNT\n\
+      \x20     9: Surrender      surrender\n\
+      \x20                       undo (in practice games)\n\
+      \x20                       log [TURNS]\n\
+      Commands can be shortened, and anything left off will be asked for.\n\
+      When asked, press return or q to cancel the order.\n\
+      Several orders separated by ; are carried out one after another,\n\
+      over as many turns as it takes.  \"macro NAME ORDERS\" saves some for later.\n\
+      Type help for more."),
+    ("directions",
+     "The Directions are:\n\
+      \x207 8 9\n\
+      \x20 \\|/\n\
+      \x204-*-6\n\
+      \x20 /|\\\n\
+      \x201 2 3"),
+    ("directions.cancel", "Or press return to cancel."),
+    ("prompt.name", "What is your name"),
+    ("prompt.captain", "Captain #{0}, what is your name"),
+    ("prompt.orders", "What are your orders, {0}"),
+    ("prompt.direction", "What direction"),
+    ("prompt.power", "Power available={0}.  Power to use"),
+    ("prompt.power.retry", "Enter 0 to {0} units, or press return to cancel."),
+    ("prompt.pan", "Pan (1-9), M for overview, or return when done"),
+    ("prompt.unsafe_launch", "Recommend you do not fire at this depth... Proceed (y/N)"),
+    ("answer.yes", "y"),
+    ("prompt.fuel", "Fuel (LBS.)"),
+    ("prompt.fuel.retry", "You have {0} LBS. left, {1}.  Enter 1 to {0}, or press return to cancel."),
+    ("prompt.depth", "New depth"),
+    ("prompt.depth.retry", "Depth is in feet, or press return to cancel."),
+    ("prompt.men", "How many men are going, {0}"),
+    ("prompt.men.retry", "Send 1 to {0} men, or press return to cancel."),
+    ("skeleton_crew", "You must leave at least {0} men on board, {1}"),
+    ("prompt.conversion", "Option?  (1=Fuel to Power, 2=Power to Fuel)"),
+    ("prompt.conversion.retry", "Enter 1 or 2, or press return to cancel."),
+    ("prompt.convert_power", "Power available: {0}.  Convert"),
+    ("prompt.convert_fuel", "Fuel available: {0}.  Convert"),
+    ("prompt.convert.retry", "Enter 0 to {0}, or press return to cancel."),
+    ("prompt.hand_over", "Pass the helm to {0}, and press return when ready"),
+    ("belay", "Belay that order, {0}."),
+    ("ships_to_sink", "You must destroy {0} enemy ships to win, {1}."),
+    ("turns_left", "{0} turns left, {1}."),
+    ("mission.orders", "Mission orders:"),
+    ("goal.sink_all", "Sink every enemy ship"),
+    ("goal.sink", "Sink {0} enemy ships"),
+    ("goal.flagship", "Sink the enemy flagship, last reported at {0}"),
+    ("goal.waypoint", "Reach the waypoint at {0}"),
+    ("goal.survive", "Stay afloat for {0} turns"),
+    ("failure.turn_limit", "Finish within {0} turns"),
+    ("failure.hq", "Keep headquarters afloat"),
+    ("failure.detected", "Keep out of sight of enemy ships"),
+    ("progress.done", "done"),
+    ("progress.to_go", "{0} to go"),
+    ("progress.afloat", "still afloat"),
+    ("progress.turn", "turn {0}"),
+    ("progress.holding", "so far so good"),
+    ("news.hq_lost", "Headquarters has been destroyed!  The mission is called off."),
+    ("news.detected", "The enemy has found us!  The mission is compromised."),
+    ("out_of_time", "Time's up!  Headquarters recalls all subs."),
+    ("status.turn", "Turn {0}"),
+    ("status.no_reports", "No reports are able to get through, {0}."),
+    ("status.no_one", "No one left to give the report, {0}."),
+    ("status.supplies",
+     "# of enemy ships left...{0}\n\
+      # of power units left...{1}\n\
+      # of torpedos  left.....{2}\n\
+      # of missiles left......{3}\n\
+      # of crewmen left.......{4}\n\
+      LBS. of fuel left.......{5}"),
+    ("status.systems", "    SYSTEM       HEALTH  (negative is bad)\n    ------       ------"),
+    ("status.last_sonar", "Last sonar contact:"),
+    ("status.position", "You are at {0}\nDepth: {1}"),
+    ("report.won", "You Won!  All hail {0} the glorious!!"),
+    ("report.ships_left",
+     "There are still {0} enemy ships left, {1}.\n\
+      You will be demoted to the rank of Deck Scrubber!!"),
+    ("report.got_them",
+     "Good work {0}, you got them all!!\n\
+      Promotion and commendations will be given immediately!"),
+    ("report.posthumously", "... albeit, posthumously."),
+    ("report.ranking", "    CAPTAIN          SHIPS SUNK"),
+    ("report.out_of_time", "Time ran out after {0} turns."),
+    ("report.hq_lost", "Headquarters was destroyed."),
+    ("report.detected", "The enemy detected the subs."),
+    ("report.accomplished",
+     "Mission accomplished, {0}!!\n\
+      Promotion and commendations will be given immediately!"),
+    ("report.mission_failed",
+     "The mission has failed, {0}.\n\
+      You will be demoted to the rank of Deck Scrubber!!"),
+    ("report.tie", "It's a tie!"),
+    ("report.most", "{0} sank the most ships!"),
+    ("and", " and "),
+    ("nav.engines_down", "Engines are under repair, {0}."),
+    ("nav.no_crew", "Not enough crew to man the engines, {0}."),
+    ("nav.no_power", "Power available={0}.  Not enough power, {1}."),
+    ("nav.supercritical",
+     "Atomic pile goes supercritical, {0}!!\n\
+      Headquarters will warn all subs to stay away\n\
+      From radioactive area!"),
+    ("nav.edge", "You can't leave the area, {0}."),
+    ("nav.aground", "You almost ran aground, {0}!"),
+    ("nav.rammed_ship", "You rammed a ship!! You're both sunk!"),
+    ("nav.rammed_hq", "You rammed your headquarters!! You're sunk!"),
+    ("nav.mine", "You've been blown up by a mine, {0}!"),
+    ("nav.eaten", "You were eaten by a sea monster, {0}!"),
+    ("nav.rammed_monster", "You rammed a sea monster!  Lucky you!"),
+    ("nav.rammed_sub", "You rammed another sub!! You're both sunk!"),
+    ("nav.eaten_passing", "You have been eaten by a sea monster, {0}!!"),
+    ("nav.narrow_escape", "You just had a narrow escape with a sea monster."),
+    ("sonar.down", "Sonar is under repair."),
+    ("sonar.no_crew", "Not enough crew to operate sonar."),
+    ("torpedo.down", "Torpedo tubes are under repair, {0}."),
+    ("torpedo.no_crew", "Not enough crew to fire torpedos, {0}."),
+    ("torpedo.none", "No torpedos left, {0}."),
+    ("torpedo.implode", "Pressure implodes sub upon firing... You're crushed!!"),
+    ("torpedo.out_of_range", "Torpedo out of range... Ineffectual {0}"),
+    ("torpedo.circled", "Torpedo circled the ocean and ran out of fuel, {0}."),
+    ("torpedo.dud", "Dud."),
+    ("torpedo.sub", "You torpedoed another sub, {0}!"),
+    ("torpedo.island", "You took out some island, {0}."),
+    ("torpedo.ship", "Ouch!  You got one, {0}!"),
+    ("torpedo.mine", "BLAM!!  Shot wasted on a mine."),
+    ("torpedo.hq", "You blew up your headquarters, {0}!"),
+    ("torpedo.monster", "A sea monster had a torpedo for lunch!"),
+    ("missile.down", "Missile silos are under repair, {0}."),
+    ("missile.no_crew", "Not enough crew left to launch a missile, {0}."),
+    ("missile.none", "No missiles left, {0}."),
+    ("missile.fuel_left", "You have {0} LBS. left, {1}."),
+    ("missile.explodes", "Missile explodes upon firing {0}!! You're Dead!!"),
+    ("missile.lost", "Missile out of sonar tracking {0}.  Missile lost."),
+    ("missile.self", "You just destroyed yourself, {0}!  Dummy!!"),
+    ("missile.sub", "You destroyed another sub, {0}!"),
+    ("missile.hq", "You've destroyed your headquarters, {0}!!"),
+    ("missile.island", "You blew out some island, {0}."),
+    ("missile.mines", "You destroyed {0} mines, {1}."),
+    ("missile.monsters", "You got {0} sea monsters, {1}!! Good work!"),
+    ("missile.ships", "You destroyed {0} enemy ships, {1}!!"),
+    ("dive.down", "Ballast controls are being repaired {0}."),
+    ("dive.no_crew", "There are not enough crew to work the controls, {0}."),
+    ("dive.crushed", "Hull crushed by pressure, {0}!!"),
+    ("resupply.hatch", "Loading hatch is damaged.  Unable to resupply, {0}."),
+    ("resupply.abandoned", "Headquarters is abandoned."),
+    ("resupply.divers", "Divers from headquarters bring out supplies and men."),
+    ("resupply.too_far", "Unable to comply with docking orders {0}."),
+    ("sabotage.hatches", "Hatches inaccessible, {0}.  No sabotage possible."),
+    ("sabotage.no_crew", "Not enough crew to go on a mission {0}."),
+    ("sabotage.nobody", "Somebody has to go, {0}."),
+    ("sabotage.in_range", "There are {0} ships in range, {1}."),
+    ("sabotage.ratio", "ratio = {0}"),
+    ("sabotage.sunk", "{0} ships were destroyed, {1}."),
+    ("sabotage.monster",
+     "A sea monster smells the men on the way back!!\n\
+      {0} men were eaten, {1}!"),
+    ("sabotage.accidents", "{0} men were lost through accidents, {1}."),
+    ("sabotage.out_of_range", "No ships in range, {0}."),
+    ("convert.down", "Power Converter is off line, {0}."),
+    ("convert.no_crew", "Not enough men to work the converter, {0}."),
+    ("convert.done", "Conversion complete.  Power={0}.  Fuel={1}"),
+    ("convert.no_power", "Not enough power to convert, {0}."),
+    ("convert.no_fuel", "Not enough fuel to convert, {0}."),
+    ("surrender", "Coward!  You're not very patriotic, {0}."),
+    ("stalemate", "Stalemate"),
+    ("collision.ship_island", "Enemy ship changed direction to avoid the island"),
+    ("collision.ship_ship", "Enemy ship changed direction to avoid another ship"),
+    ("collision.rammed", "You've been rammed by a ship!"),
+    ("collision.hq_rammed", "Your headquarters was rammed!"),
+    ("collision.ship_mine", "Enemy ship changed direction to avoid mine"),
+    ("collision.ship_mined", "Enemy ship was destroyed by a mine!"),
+    ("collision.ship_eaten", "Enemy ship was eaten by a monster!"),
+    ("collision.monster_island", "Sea monster changed direction to avoid the island"),
+    ("collision.eaten", "You've been eaten by a sea monster!"),
+    ("collision.hq_eaten", "A sea monster ate your headquarters!"),
+    ("collision.ship_eaten_moving", "Ship eaten by a moving monster!"),
+    ("collision.monster_mined", "Sea monster destroyed by a mine!"),
+    ("collision.monster_fight", "A sea monster fight!!"),
+    ("collision.tie", "It's a tie!"),
+    ("collision.one_dies", "And one dies!!"),
+    ("collision.edge", "{0} changed direction to stay in the area."),
+    ("attack.firing_bearing", "Enemy ship {0} firing..."),
+    ("attack.firing", "Enemy ship at {0} firing..."),
+    ("attack.threat", "Threat: {0}"),
+    ("attack.depth_charges", "Depth charges off {0} side, {1}!"),
+    ("attack.no_damage", "No real damage sustained, {0}."),
+    ("attack.light", "Light, superficial damage sustained, {0}!"),
+    ("attack.moderate", "Moderate damange, repairs needed, {0}!!"),
+    ("attack.heavy", "Heavy damage!! Repairs immediate, {0}!!"),
+    ("attack.critical",
+     "Damage Critical!!!  We need help!!!\n\
+      Send 'HELP' in code.  Here is the code: "),
+    ("attack.garbled", "The code comes through garbled."),
+    ("turn_summary",
+     "Turn {0}.  Depth {1}, power {2}, fuel {3}, {4} torpedos, {5} missiles, {6} crew."),
+    ("under_repair", "Under repair: {0}."),
+    ("undo.nothing", "Nothing further to undo, {0}."),
+    ("undo.real_war", "There's no undo in a real war, {0}."),
+    ("crew_gives_up", "The crew gives up waiting for sensible orders, {0}."),
+    ("pile_dead", "Atomic pile has gone dead!! Sub sinks, crew suffocates."),
+    ("sub_lost", "{0}'s sub has gone down with all hands."),
+    ("waiting_for", "Waiting for {0}'s orders."),
+    ("undo.back", "Back to the start of turn {0}."),
+    ("port", "port"),
+    ("starboard", "starboard"),
+    ("system.engines", "Engines"),
+    ("system.sonar", "Sonar"),
+    ("system.torpedos", "Torpedos"),
+    ("system.missiles", "Missiles"),
+    ("system.manuevering", "Manuevering"),
+    ("system.computers", "Computers"),
+    ("system.resupply", "Resupply"),
+    ("system.sabotage", "Sabotage"),
+    ("system.converter", "Converter"),
+    ("order.none", "No orders given"),
+    ("order.no_such_command", "There is no command {0}"),
+    ("order.unknown", "Unknown command '{0}'"),
+    ("order.ambiguous", "'{0}' could be {1}"),
+    ("or", " or "),
+    ("order.not_a", "'{0}' is not a {1}"),
+    ("order.quantity", "quantity"),
+    ("order.depth", "depth"),
+    ("order.men", "number of men"),
+    ("order.turns", "number of turns"),
+    ("order.not_a_direction", "'{0}' is not a direction"),
+    ("order.conversion", "Convert fuel or power, not '{0}'"),
+    ("order.extra", "Not sure what '{0}' means"),
+    ("legend.own", "your sub"),
+    ("legend.other", "another player's sub"),
+    ("legend.ship", "enemy ship"),
+    ("legend.monster", "sea monster"),
+    ("legend.mine", "mine"),
+    ("legend.hq", "headquarters"),
+    ("legend.island", "island"),
+    ("noun.sub", "Sub"),
+    ("nouns.sub", "subs"),
+    ("noun.island", "Island"),
+    ("nouns.island", "islands"),
+    ("noun.ship", "Enemy ship"),
+    ("nouns.ship", "enemy ships"),
+    ("noun.mine", "Mine"),
+    ("nouns.mine", "mines"),
+    ("noun.hq", "Headquarters"),
+    ("nouns.hq", "headquarters"),
+    ("noun.monster", "Sea monster"),
+    ("nouns.monster", "sea monsters"),
+    ("compass.n", "north"),
+    ("compass.ne", "northeast"),
+    ("compass.e", "east"),
+    ("compass.se", "southeast"),
+    ("compass.s", "south"),
+    ("compass.sw", "southwest"),
+    ("compass.w", "west"),
+    ("compass.nw", "northwest"),
+    ("bearing.here", "right here"),
+    ("bearing.cell", "1 cell {0}"),
+    ("bearing.cells", "{0} cells {1}"),
+    ("sonar.nothing", "Sonar picks up nothing."),
+    ("sonar.contacts", "Sonar contacts, nearest first:"),
+    ("sonar.contact", "{0} {1}."),
+    ("sonar.several", "{0} {1} {2}."),
+    ("sonar.far_islands", "{0} more island cells further off."),
+    ("overview", "Overview, each cell is {0}x{0}"),
+    ("sector", "Sector {0}-{1} east, {2}-{3} south of {4}x{5}"),
+    ("help.index",
+     "Type help followed by a command or topic, e.g. \"help torpedo\".\n\
+      Commands: navigate sonar torpedo missile manuever status\n\
+      \x20         resupply sabotage convert surrender undo log\n\
+      Topics:"),
+    ("help.navigate",
+     "NAVIGATE DIRECTION POWER\n\
+      Moves one cell for every {0} units of power.  Needs {1} crew and working\n\
+      engines.  At {2} feet or shallower the sub is slower.  Putting more than\n\
+      {3} units through the pile at once risks a {4}% chance of it going\n\
+      supercritical.  Running into a ship, mine or headquarters sinks the sub,\n\
+      and islands stop it."),
+    ("help.sonar",
+     "SONAR\n\
+      Charts everything in the ocean, for {0} units of power.  Needs {1} crew.\n\
+      Doesn't end the turn.  See also: help chart"),
+    ("help.torpedo",
+     "TORPEDO DIRECTION\n\
+      Fires a torpedo in a straight line, for {0} units of power.  Needs {1} crew.\n\
+      It runs {2} to {3} cells, {4} more below {5} feet, and hits the first\n\
+      thing in its way.  Mines and monsters shrug them off.  Firing at {6} feet\n\
+      or deeper may implode the sub."),
+    ("help.missile",
+     "MISSILE DIRECTION FUEL\n\
+      Flies one cell for every {0} LBS. of fuel, then destroys everything\n\
+      within a cell of where it lands, your own sub and headquarters\n\
+      included.  Needs {1} crew.  Launching at {2} feet or shallower, or {3} or\n\
+      deeper, may blow up the sub."),
+    ("help.manuever",
+     "MANUEVER DEPTH  (or DIVE DEPTH)\n\
+      Changes depth, using a unit of power for every {0} feet.  Needs {1} crew.\n\
+      The hull is crushed at {2} feet.  See also: help depth"),
+    ("help.status",
+     "STATUS\n\
+      Reports supplies, the health of each system, and the last sonar\n\
+      picture.  Needs {0} crew.  Doesn't end the turn."),
+    ("help.resupply",
+     "RESUPPLY\n\
+      Docks with headquarters, which must be within {0} cells, at {1} feet or\n\
+      shallower.  {2} {3} power, {4} torpedos, {5} missiles,\n\
+      {6} LBS. of fuel and {7} crew.  Headquarters only has so many loads."),
+    ("help.sabotage",
+     "SABOTAGE MEN\n\
+      Sends men to sink ships within {0} cells.  Needs {1} crew, and {2} must\n\
+      stay aboard.  More men sink more ships, but each only has a {3}% chance\n\
+      of getting back, less with monsters about."),
+    ("help.convert",
+     "CONVERT FUEL|POWER AMOUNT\n\
+      Turns {0} LBS. of fuel into a unit of power, or a unit of power into {1}\n\
+      LBS. of fuel.  Needs {2} crew."),
+    ("help.surrender",
+     "SURRENDER\n\
+      Ends the game for your sub.  Not recommended."),
+    ("help.undo",
+     "UNDO\n\
+      In practice games, goes back to the start of the previous turn."),
+    ("help.log",
+     "LOG [TURNS]\n\
+      Reads back the captain's log: orders carried out and what they used,\n\
+      sonar contacts, depth charges taken and ships sunk, for the whole\n\
+      game or just the last few turns.  Doesn't end the turn.  The log can\n\
+      be saved when the game ends with --log FILE, as JSON if FILE ends in\n\
+      .json."),
+    ("help.north", "North is up the chart."),
+    ("help.sonar_shows", "Sonar shows:"),
+    ("help.chart",
+     "Large oceans are shown a section at a time, with an overview where\n\
+      each cell stands for several.\n\
+      Started with --accessible, sonar reads out each contact's distance\n\
+      and bearing instead, nearest first."),
+    ("help.enemies",
+     "Enemy ships wander the ocean, and any within {0} cells drop depth\n\
+      charges after your turn.  A ship moving into the sub sinks it.\n\
+      Sea monsters eat anything they run into, ships included.  Passing within\n\
+      {1} cells of one gives it a {2}% chance at the sub.\n\
+      Mines stay put.  Ships usually steer clear of them; subs should too."),
+    ("help.damage",
+     "Depth charges use up power and damage systems.  A system with\n\
+      negative health is out of action until repaired:"),
+    ("help.repairs",
+     "Repairs happen every turn, twice as fast at {0} feet or shallower, or\n\
+      deeper than {1} feet."),
+    ("help.depth",
+     "{0} feet or shallower: slower, torpedos run short, missiles risky,\n\
+      \x20   repairs are quicker, and headquarters can resupply the sub.\n\
+      Deeper than {1} feet: torpedos run {2} cells further.\n\
+      {3} feet or deeper: torpedos and missiles may destroy the sub.\n\
+      {4} feet: crushed."),
+    ("help.orders",
+     "Orders can be typed whole, \"nav 9 300\", or in part, \"nav\", and\n\
+      whatever is missing is asked for.  Press return or q at a question\n\
+      to call the order off.\n\
+      Several orders separated by ; are carried out in turn, \"dive 100;\n\
+      sonar; torp 6\".  They're held if the sub comes under fire, something\n\
+      comes close, or power runs low.\n\
+      \"macro NAME ORDERS\" saves orders to give again by NAME.  \"macro\"\n\
+      lists them, and \"macro NAME\" forgets one."),
+    ("help.missions",
+     "The usual mission is to sink every enemy ship.  Started with\n\
+      --mission, every goal given has to be met to win:\n\
+      \x20   sink=N      sink N ships          flagship    sink the flagship\n\
+      \x20   waypoint    reach a waypoint      survive=N   stay afloat N turns\n\
+      and any failure given ends the game in defeat:\n\
+      \x20   turns=N     the goals aren't met within N turns\n\
+      \x20   hq          headquarters is destroyed\n\
+      \x20   undetected  a ship comes alongside, or within {0} cells while the\n\
+      \x20               sub is at {1} feet or shallower\n\
+      A status report shows how each part of the mission is going."),
+    ("help.sets_stocks", "Sets stocks to"),
+    ("help.tops_up", "Brings stocks up to"),
+    ("topic.directions", "which way is which"),
+    ("topic.chart", "what the sonar symbols mean"),
+    ("topic.enemies", "ships, monsters and mines"),
+    ("topic.damage", "depth charges and repairs"),
+    ("topic.depth", "what depth changes"),
+    ("topic.orders", "typing orders, queues and macros"),
+    ("topic.missions", "goals, and what fails a mission"),
+    ("cause.mine", "blown up by a mine"),
+    ("cause.monster", "eaten by a sea monster"),
+    ("cause.rammed", "sunk in a collision"),
+    ("cause.supercritical", "lost when the pile went supercritical"),
+    ("cause.power_exhausted", "sank when the power ran out"),
+    ("cause.retaliation", "sank when depth charges drained the power"),
+    ("cause.crushed", "crushed by the deep"),
+    ("cause.misfire", "destroyed by its own weapons"),
+    ("cause.friendly_fire", "sunk by another sub"),
+    ("cause.surrendered", "surrendered"),
+    ("weapon.torpedo", "torpedo"),
+    ("weapon.missile", "missile"),
+    ("weapon.sabotage", "sabotage"),
+    ("weapon.ramming", "ramming"),
+    ("debrief.title", "AFTER-ACTION REPORT"),
+    ("ending.all_sunk", "every enemy ship sunk"),
+    ("ending.accomplished", "the mission accomplished"),
+    ("ending.out_of_time", "time running out"),
+    ("ending.hq_lost", "headquarters destroyed"),
+    ("ending.detected", "the subs detected by the enemy"),
+    ("ending.all_lost", "no subs left afloat"),
+    ("ending.called_off", "the game was called off"),
+    ("debrief.lasted", "The action lasted {0} turns and ended with {1}."),
+    ("debrief.wrecks", "The sea took {0} enemy ships: {1} to mines, {2} to sea monsters."),
+    ("debrief.survived", "    Survived all {0} turns."),
+    ("debrief.lost", "    Out of action: {0}."),
+    ("debrief.sunk_by", "{0} by {1}"),
+    ("none", "none"),
+    ("lbs", "{0} LBS."),
+    ("debrief.ships_sunk", "Ships sunk:"),
+    ("debrief.torpedos", "Torpedos fired:"),
+    ("debrief.missiles", "Missiles fired:"),
+    ("debrief.fuel", "Fuel used:"),
+    ("debrief.crew_lost", "Crew lost:"),
+    ("debrief.resupplies", "Resupplies:"),
+    ("debrief.damage", "Damage taken:"),
+    ("score.ships_sunk", "ships sunk"),
+    ("score.victory", "victory"),
+    ("score.speed", "speed"),
+    ("score.crew", "crew aboard"),
+    ("score.supplies", "supplies left"),
+    ("score.hq_lost", "headquarters lost"),
+    ("score.surrender", "surrender"),
+    ("score.difficulty", "difficulty"),
+    ("score.total", "Score for {0}: {1}"),
+    ("score.table",
+     "    HIGH SCORES\n\
+      \x20   RANK  CAPTAIN            SCORE  GAMES"),
+    ("rank.deck_scrubber", "Deck Scrubber"),
+    ("rank.ensign", "Ensign"),
+    ("rank.lieutenant", "Lieutenant"),
+    ("rank.lieutenant_commander", "Lieutenant Commander"),
+    ("rank.commander", "Commander"),
+    ("rank.captain", "Captain"),
+    ("rank.commodore", "Commodore"),
+    ("rank.admiral", "Admiral"),
+    ("medal.navy_cross", "Navy Cross"),
+    ("medal.navy_cross.for", "clearing the ocean of enemy ships"),
+    ("medal.silver_star", "Silver Star"),
+    ("medal.silver_star.for", "sinking 10 ships in one action"),
+    ("medal.torpedo_ace", "Torpedo Ace"),
+    ("medal.torpedo_ace.for", "sinking 5 ships by torpedo"),
+    ("medal.saboteurs_star", "Saboteur's Star"),
+    ("medal.saboteurs_star.for", "sinking 3 ships by sabotage"),
+    ("medal.swift_victory", "Swift Victory"),
+    ("medal.swift_victory.for", "winning within 30 turns"),
+    ("medal.purple_heart", "Purple Heart"),
+    ("medal.purple_heart.for", "going down fighting"),
+    ("career.awarded", "{0} is awarded the {1} for {2}."),
+    ("career.promoted", "{0} is promoted to {1}!"),
+    ("career.demoted", "{0} is demoted to the rank of {1}!!"),
+    ("career.totals", "    {0} actions, {1} victories, {2} ships sunk, {3} points (best {4})"),
+    ("career.next_rank", "    Promotion to {0} at {1} victories and {2} points."),
+    ("career.recent", "    Recent actions:"),
+    ("career.victory", "victory"),
+    ("career.defeat", "defeat"),
+    ("career.action", "        {0} {1} turns {2} ships {3} points"),
+    ("stock.power", "power"),
+    ("stock.fuel", "fuel"),
+    ("stock.torpedos", "torpedos"),
+    ("stock.missiles", "missiles"),
+    ("stock.crew", "crew"),
+    ("severity.no", "no"),
+    ("severity.light", "light"),
+    ("severity.moderate", "moderate"),
+    ("severity.heavy", "heavy"),
+    ("severity.critical", "critical"),
+    ("log.sunk", ", {0} ships sunk"),
+    ("log.contacts", "sonar: {0} ships, {1} monsters, {2} mines, {3} other subs"),
+    ("log.hit", "depth charges, {0} damage"),
+    ("log.power", ", power -{0}"),
+    ("log.systems", ", hit {0}"),
+    ("log.lost", "sub lost: {0}"),
+    ("log.turn", "Turn {0}"),
+    ("log.empty", "Nothing logged yet."),
+    ("log.of", "Log of {0}"),
+    ("macro.none", "No macros yet.  Try: macro attack dive 100; sonar; torpedo 6"),
+    ("macro.command", "'{0}' is already a command."),
+    ("macro.forgotten", "Macro {0} forgotten."),
+    ("macro.saved", "Macro {0} saved."),
+    ("alert.attack", "We're under attack"),
+    ("alert.power", "Power is down to {0}"),
+    ("alert.contact", "Sonar contact at {0}"),
+    ("alert.holding", "{0}!  Holding the remaining {1} orders, {2}."),
+    ("queued", "{0}, with {1} more to follow."),
+    ("difficulty.unknown", "'{0}' isn't one of {1} or custom"),
+    ("difficulty.expected", "Expected NAME=VALUE, not '{0}'"),
+    ("difficulty.range", "{0} wants a number or a range like 10-20, not '{1}'"),
+    ("difficulty.number", "{0} wants a number above 0, not '{1}'"),
+    ("difficulty.count", "{0} wants a whole number, like 2, not '{1}'"),
+    ("difficulty.no_ships", "There has to be at least one ship"),
+    ("difficulty.key",
+     "Can't change '{0}'.  Try ships, mines, monsters, threat, repair, resupplies or reward"),
+    ("difficulty.describe",
+     "{0}: {1}-{2} ships, {3}-{4} mines, {5} monsters, threat x{6}, repairs x{7}, {8} resupplies, \
+      score x{9}"),
+    ("difficulty", "Difficulty {0}"),
+    ("house_rules", "House rules: {0}"),
+    ("personal_best", "A personal best, {0}!"),
+    ("save.scores", "Unable to save the high scores to {0}: {1}"),
+    ("save.log", "Unable to save the log to {0}: {1}"),
+    ("save.careers", "Unable to save the careers to {0}: {1}"),
+    ("session",
+     "    THIS SESSION, {0} GAMES\n\
+      \x20   CAPTAIN            WINS  SHIPS  TOTAL SCORE"),
+    ("again",
+     "Another game?  1: new game,  2: replay this one (seed {0}),\n\
+      \x20              3: a different ocean,  return to stop"),
+    ("again.choice", "Your choice"),
+    ("again.retry", "Choose 1, 2 or 3, or press return to stop."),
+    ("scenario.size", "Size of the ocean ({0}x{1})"),
+    ("scenario.size.retry", "Width and height are {0} to {1}, e.g. 30x20."),
+    ("scenario.wrap.on", "Wrap around at the edges (Y/n)"),
+    ("scenario.wrap.off", "Wrap around at the edges (y/N)"),
+    ("scenario.difficulty", "Difficulty ({0})"),
+    ("net.lost", "Lost contact with {0}.  Game abandoned."),
+    ("net.listen", "Unable to listen on port {0}: {1}"),
+    ("net.waiting", "Waiting for captain #{0} to join on port {1}..."),
+    ("net.joined", "Captain #{0} joined from {1}."),
+    ("net.failed", "Failed connection: {0}"),
+    ("net.unreachable", "Unable to reach {0}: {1}"),
+    ("net.connected", "Connected to {0}.  Waiting for the game to start..."),
+    ("net.closed", "Connection closed."),
+    ("missing.direction", "Missing direction"),
+    ("missing.power", "Missing power"),
+    ("missing.fuel", "Missing fuel"),
+    ("missing.depth", "Missing depth"),
+    ("missing.men", "Missing men"),
+    ("missing.conversion", "Missing fuel or power"),
+    ("missing.amount", "Missing amount"),
+    ("mission.number", "{0} wants a number above 0, e.g. {0}=10"),
+    ("mission.unknown",
+     "'{0}' isn't part of any mission.  Try all, sink=N, flagship, waypoint, survive=N, turns=N, \
+      hq or undetected"),
+    ("rules.value", "{0} can't be '{1}'"),
+    ("rules.unknown", "there's no rule called '{0}'"),
+    ("rules.expected", "expected NAME = VALUE, not '{0}'"),
+    ("rules.line", "{0}, line {1}: {2}"),
+    ("rules.chance", "{0} is a chance, from 0 to 1"),
+    ("rules.negative", "{0} can't be below 0"),
+    ("rules.above_zero", "{0} has to be above 0"),
+    ("rules.depths", "depths have to go 0 < shallow_depth < deep_depth < crush_depth"),
+    ("rules.sabotage", "saboteurs have to be able to leave: skeleton_crew < sabotage_crew"),
+    ("rules.torpedos", "torpedos have to run at least a cell: 0 <= torpedo_spread < torpedo_range"),
+    ("usage",
+     "Usage: rust_seawar [--size WIDTHxHEIGHT] [--wrap] [--players N] [--ai N] [--versus]\n\
+      \x20                  [--host PORT | --join ADDRESS] [--seed N] [--bot]\n\
+      \x20                  [--practice] [--undo N] [--lang CODE] [--accessible]\n\
+      \x20                  [--log FILE] [--scores FILE | --no-scores]\n\
+      \x20                  [--career | --career-file FILE] [--difficulty SPEC]\n\
+      \x20                  [--rules FILE] [--show-rules] [--classic]\n\
+      \x20                  [--mission SPEC] [--turns N] [--sink N]"),
+    ("usage.size",
+     "  --size     Size of the ocean, {0} to {1} cells each way (default 20x20)\n\
+      \x20            Ships, mines and monsters go up with the area"),
+    ("usage.wrap", "  --wrap     The ocean wraps around at the edges"),
+    ("usage.players", "  --players  Number of subs taking turns at this terminal, 1 to {0}"),
+    ("usage.ai", "  --ai       How many of those subs the computer commands"),
+    ("usage.versus", "  --versus   Captains compete for the most ships sunk"),
+    ("usage.host", "  --host     Wait for the other captains to join on PORT"),
+    ("usage.join", "  --join     Join the game hosted at ADDRESS, e.g. localhost:7070"),
+    ("usage.seed", "  --seed     Replay the game started from seed N"),
+    ("usage.bot",
+     "  --bot      A program takes the helm, speaking the bot protocol on stdin/stdout"),
+    ("usage.practice", "  --practice A practice game, where the last {0} turns can be undone"),
+    ("usage.undo", "  --undo     A practice game, where the last N turns can be undone"),
+    ("usage.lang", "  --lang     Language to play in, en or es (default from LANG)"),
+    ("usage.log",
+     "  --log      Save the captain's log to FILE at the end, as JSON if it ends in .json\n\
+      \x20            Later games go to a file each: FILE-2, FILE-3... before any extension"),
+    ("usage.scores", "  --scores   Keep the high score table in FILE (default ~/.seawar_scores)"),
+    ("usage.no_scores", "  --no-scores  Don't keep high scores"),
+    ("usage.career", "  --career   Captains keep their rank and medals from game to game"),
+    ("usage.career_file", "  --career-file  Keep careers in FILE (default ~/.seawar_career)"),
+    ("usage.difficulty",
+     "  --difficulty  cadet, lieutenant, commander (default), captain or admiral,\n\
+      \x20            or custom, with changes to ships, mines, monsters, threat, repair,\n\
+      \x20            resupplies or reward, e.g. captain,monsters=2,repair=1.5"),
+    ("usage.mission",
+     "  --mission  What it takes to win, and to lose, as a list of:\n\
+      \x20              all         sink every enemy ship (unless other goals are given)\n\
+      \x20              sink=N      sink N enemy ships\n\
+      \x20              flagship    sink the enemy flagship\n\
+      \x20              waypoint    reach a waypoint\n\
+      \x20              survive=N   stay afloat for N turns\n\
+      \x20              turns=N     lose if the goals aren't met within N turns\n\
+      \x20              hq          lose if headquarters is destroyed\n\
+      \x20              undetected  lose if an enemy ship spots a sub: one alongside, or\n\
+      \x20                          one in range while the sub is near the surface\n\
+      \x20            e.g. --mission sink=10,turns=30,hq"),
+    ("usage.turns", "  --turns    Same as --mission turns=N"),
+    ("usage.sink", "  --sink     Same as --mission sink=N"),
+    ("usage.rules", "  --rules    Play by the house rules in FILE"),
+    ("usage.classic", "  --classic  Play by the original BASIC game's rules where they differ"),
+    ("usage.show_rules", "  --show-rules  Print the rules in play, as a rules file to start from"),
+    ("usage.accessible",
+     "  --accessible  Describe sonar in words, for screen readers, without animation"),
+];
+
+const SPANISH: [(&str, &str); 497] = [
+    ("menu",
+     "Las órdenes son:\n\
+      \x20     0: Navegar          navigate DIRECCIÓN ENERGÍA\n\
+      \x20     1: Sonar            sonar\n\
+      \x20     2: Lanzar torpedo   torpedo DIRECCIÓN\n\
+      \x20     3: Lanzar misil     missile DIRECCIÓN COMBUSTIBLE\n\
+      \x20     4: Maniobrar        dive PROFUNDIDAD\n\
+      \x20     5: Estado           status\n\
+      \x20     6: Reabastecer      resupply\n\
+      \x20     7: Sabotaje         sabotage HOMBRES\n\
+      \x20     8: Convertir        convert fuel|power CANTIDAD\n\
+      \x20     9: Rendirse         surrender\n\
+      \x20                         undo (en partidas de práctica)\n\
+      \x20                         log [TURNOS]\n\
+      Las órdenes se pueden abreviar, y se preguntará por lo que falte.\n\
+      Al preguntar, pulse intro o q para cancelar la orden.\n\
+      Varias órdenes separadas por ; se cumplen una tras otra, durante los\n\
+      turnos que haga falta.  \"macro NOMBRE ÓRDENES\" las guarda para después.\n\
+      Escriba help para más información."),
+    ("directions",
+     "Las direcciones son:\n\
+      \x207 8 9\n\
+      \x20 \\|/\n\
+      \x204-*-6\n\
+      \x20 /|\\\n\
+      \x201 2 3"),
+    ("directions.cancel", "O pulse intro para cancelar."),
+    ("prompt.name", "¿Cómo se llama"),
+    ("prompt.captain", "Capitán n.º {0}, ¿cómo se llama"),
+    ("prompt.orders", "¿Cuáles son sus órdenes, {0}"),
+    ("prompt.direction", "¿En qué dirección"),
+    ("prompt.power", "Energía disponible={0}.  ¿Energía a usar"),
+    ("prompt.power.retry", "Indique de 0 a {0} unidades, o pulse intro para cancelar."),
+    ("prompt.pan", "Desplazar (1-9), M para vista general, o intro para terminar"),
+    ("prompt.unsafe_launch", "No se recomienda disparar a esta profundidad... ¿Continuar (s/N)"),
+    ("answer.yes", "s"),
+    ("prompt.fuel", "¿Combustible (LBS.)"),
+    ("prompt.fuel.retry",
+     "Le quedan {0} LBS., {1}.  Indique de 1 a {0}, o pulse intro para cancelar."),
+    ("prompt.depth", "¿Nueva profundidad"),
+    ("prompt.depth.retry", "La profundidad va en pies, o pulse intro para cancelar."),
+    ("prompt.men", "¿Cuántos hombres van, {0}"),
+    ("prompt.men.retry", "Envíe de 1 a {0} hombres, o pulse intro para cancelar."),
+    ("skeleton_crew", "Deben quedar al menos {0} hombres a bordo, {1}"),
+    ("prompt.conversion", "¿Opción?  (1=Combustible a energía, 2=Energía a combustible)"),
+    ("prompt.conversion.retry", "Indique 1 o 2, o pulse intro para cancelar."),
+    ("prompt.convert_power", "Energía disponible: {0}.  ¿Cuánta convertir"),
+    ("prompt.convert_fuel", "Combustible disponible: {0}.  ¿Cuánto convertir"),
+    ("prompt.convert.retry", "Indique de 0 a {0}, o pulse intro para cancelar."),
+    ("prompt.hand_over", "Ceda el timón a {0}, y pulse intro cuando esté listo"),
+    ("belay", "Orden anulada, {0}."),
+    ("ships_to_sink", "Debe destruir {0} barcos enemigos para ganar, {1}."),
+    ("turns_left", "Quedan {0} turnos, {1}."),
+    ("mission.orders", "Órdenes de la misión:"),
+    ("goal.sink_all", "Hundir todos los barcos enemigos"),
+    ("goal.sink", "Hundir {0} barcos enemigos"),
+    ("goal.flagship", "Hundir el buque insignia enemigo, visto por última vez en {0}"),
+    ("goal.waypoint", "Llegar al punto de encuentro en {0}"),
+    ("goal.survive", "Seguir a flote durante {0} turnos"),
+    ("failure.turn_limit", "Terminar en {0} turnos"),
+    ("failure.hq", "Mantener a flote el cuartel general"),
+    ("failure.detected", "No dejarse ver por barcos enemigos"),
+    ("progress.done", "hecho"),
+    ("progress.to_go", "faltan {0}"),
+    ("progress.afloat", "sigue a flote"),
+    ("progress.turn", "turno {0}"),
+    ("progress.holding", "de momento, bien"),
+    ("news.hq_lost", "¡El cuartel general ha sido destruido!  Se suspende la misión."),
+    ("news.detected", "¡El enemigo nos ha encontrado!  La misión está comprometida."),
+    ("out_of_time", "¡Se acabó el tiempo!  El cuartel general llama a todos los submarinos."),
+    ("status.turn", "Turno {0}"),
+    ("status.no_reports", "No llega ningún informe, {0}."),
+    ("status.no_one", "No queda nadie para dar el informe, {0}."),
+    ("status.supplies",
+     "Barcos enemigos restantes...{0}\n\
+      Unidades de energía........{1}\n\
+      Torpedos restantes.........{2}\n\
+      Misiles restantes..........{3}\n\
+      Tripulantes restantes......{4}\n\
+      LBS. de combustible........{5}"),
+    ("status.systems", "    SISTEMA      ESTADO  (negativo es malo)\n    -------      ------"),
+    ("status.last_sonar", "Último contacto de sonar:"),
+    ("status.position", "Está en {0}\nProfundidad: {1}"),
+    ("report.won", "¡Ha ganado!  ¡¡Gloria a {0}!!"),
+    ("report.ships_left",
+     "Aún quedan {0} barcos enemigos, {1}.\n\
+      ¡¡Será degradado al rango de friegacubiertas!!"),
+    ("report.got_them",
+     "¡¡Buen trabajo, {0}, los hundió todos!!\n\
+      ¡Recibirá ascenso y condecoraciones de inmediato!"),
+    ("report.posthumously", "... aunque sea a título póstumo."),
+    ("report.ranking", "    CAPITÁN          BARCOS HUNDIDOS"),
+    ("report.out_of_time", "Se acabó el tiempo tras {0} turnos."),
+    ("report.hq_lost", "El cuartel general fue destruido."),
+    ("report.detected", "El enemigo detectó a los submarinos."),
+    ("report.accomplished",
+     "¡¡Misión cumplida, {0}!!\n\
+      ¡Recibirá ascenso y condecoraciones de inmediato!"),
+    ("report.mission_failed",
+     "La misión ha fracasado, {0}.\n\
+      ¡¡Será degradado al rango de friegacubiertas!!"),
+    ("report.tie", "¡Empate!"),
+    ("report.most", "¡{0} hundió más barcos!"),
+    ("and", " y "),
+    ("nav.engines_down", "Los motores están en reparación, {0}."),
+    ("nav.no_crew", "No hay tripulación para los motores, {0}."),
+    ("nav.no_power", "Energía disponible={0}.  No hay energía suficiente, {1}."),
+    ("nav.supercritical",
+     "¡¡La pila atómica entra en estado supercrítico, {0}!!\n\
+      El cuartel general avisará a todos los submarinos que se alejen\n\
+      ¡de la zona radiactiva!"),
+    ("nav.edge", "No puede salir de la zona, {0}."),
+    ("nav.aground", "¡Casi encalla, {0}!"),
+    ("nav.rammed_ship", "¡¡Embistió un barco!! ¡Se hunden los dos!"),
+    ("nav.rammed_hq", "¡¡Embistió su cuartel general!! ¡Se hunde!"),
+    ("nav.mine", "¡Una mina le ha hecho volar por los aires, {0}!"),
+    ("nav.eaten", "¡Se lo ha comido un monstruo marino, {0}!"),
+    ("nav.rammed_monster", "¡Embistió un monstruo marino!  ¡Qué suerte!"),
+    ("nav.rammed_sub", "¡¡Embistió otro submarino!! ¡Se hunden los dos!"),
+    ("nav.eaten_passing", "¡¡Se lo ha comido un monstruo marino, {0}!!"),
+    ("nav.narrow_escape", "Se ha librado por poco de un monstruo marino."),
+    ("sonar.down", "El sonar está en reparación."),
+    ("sonar.no_crew", "No hay tripulación para manejar el sonar."),
+    ("torpedo.down", "Los tubos lanzatorpedos están en reparación, {0}."),
+    ("torpedo.no_crew", "No hay tripulación para lanzar torpedos, {0}."),
+    ("torpedo.none", "No quedan torpedos, {0}."),
+    ("torpedo.implode", "La presión hace implosionar el submarino al disparar... ¡¡Aplastado!!"),
+    ("torpedo.out_of_range", "Torpedo fuera de alcance... Inútil, {0}"),
+    ("torpedo.circled", "El torpedo dio la vuelta al océano y se quedó sin combustible, {0}."),
+    ("torpedo.dud", "Falló la espoleta."),
+    ("torpedo.sub", "¡Torpedeó a otro submarino, {0}!"),
+    ("torpedo.island", "Se llevó un trozo de isla, {0}."),
+    ("torpedo.ship", "¡Zas!  ¡Le dio a uno, {0}!"),
+    ("torpedo.mine", "¡¡BUM!!  Disparo malgastado en una mina."),
+    ("torpedo.hq", "¡Voló su cuartel general, {0}!"),
+    ("torpedo.monster", "¡Un monstruo marino se almorzó un torpedo!"),
+    ("missile.down", "Los silos de misiles están en reparación, {0}."),
+    ("missile.no_crew", "No queda tripulación para lanzar un misil, {0}."),
+    ("missile.none", "No quedan misiles, {0}."),
+    ("missile.fuel_left", "Le quedan {0} LBS., {1}."),
+    ("missile.explodes", "¡¡El misil explota al disparar, {0}!! ¡¡Ha muerto!!"),
+    ("missile.lost", "El misil se sale del alcance del sonar, {0}.  Misil perdido."),
+    ("missile.self", "¡Se ha destruido a sí mismo, {0}!  ¡¡Torpe!!"),
+    ("missile.sub", "¡Destruyó otro submarino, {0}!"),
+    ("missile.hq", "¡¡Destruyó su cuartel general, {0}!!"),
+    ("missile.island", "Voló un trozo de isla, {0}."),
+    ("missile.mines", "Destruyó {0} minas, {1}."),
+    ("missile.monsters", "¡¡Acabó con {0} monstruos marinos, {1}!! ¡Buen trabajo!"),
+    ("missile.ships", "¡¡Destruyó {0} barcos enemigos, {1}!!"),
+    ("dive.down", "Se están reparando los controles de lastre, {0}."),
+    ("dive.no_crew", "No hay tripulación para los controles, {0}."),
+    ("dive.crushed", "¡¡La presión aplasta el casco, {0}!!"),
+    ("resupply.hatch", "La escotilla de carga está dañada.  No se puede reabastecer, {0}."),
+    ("resupply.abandoned", "El cuartel general está abandonado."),
+    ("resupply.divers", "Buzos del cuartel general traen suministros y hombres."),
+    ("resupply.too_far", "No es posible cumplir las órdenes de atraque, {0}."),
+    ("sabotage.hatches", "Escotillas inaccesibles, {0}.  No es posible el sabotaje."),
+    ("sabotage.no_crew", "No hay tripulación para una misión, {0}."),
+    ("sabotage.nobody", "Alguien tiene que ir, {0}."),
+    ("sabotage.in_range", "Hay {0} barcos a su alcance, {1}."),
+    ("sabotage.ratio", "proporción = {0}"),
+    ("sabotage.sunk", "Se destruyeron {0} barcos, {1}."),
+    ("sabotage.monster",
+     "¡¡Un monstruo marino huele a los hombres a la vuelta!!\n\
+      ¡Se comió a {0} hombres, {1}!"),
+    ("sabotage.accidents", "Se perdieron {0} hombres en accidentes, {1}."),
+    ("sabotage.out_of_range", "No hay barcos a su alcance, {0}."),
+    ("convert.down", "El convertidor de energía no funciona, {0}."),
+    ("convert.no_crew", "No hay hombres para el convertidor, {0}."),
+    ("convert.done", "Conversión completa.  Energía={0}.  Combustible={1}"),
+    ("convert.no_power", "No hay energía suficiente para convertir, {0}."),
+    ("convert.no_fuel", "No hay combustible suficiente para convertir, {0}."),
+    ("surrender", "¡Cobarde!  No es usted muy patriota, {0}."),
+    ("stalemate", "Punto muerto"),
+    ("collision.ship_island", "Un barco enemigo cambió de rumbo para evitar la isla"),
+    ("collision.ship_ship", "Un barco enemigo cambió de rumbo para evitar otro barco"),
+    ("collision.rammed", "¡Un barco le ha embestido!"),
+    ("collision.hq_rammed", "¡Han embestido su cuartel general!"),
+    ("collision.ship_mine", "Un barco enemigo cambió de rumbo para evitar una mina"),
+    ("collision.ship_mined", "¡Una mina destruyó un barco enemigo!"),
+    ("collision.ship_eaten", "¡Un monstruo se comió un barco enemigo!"),
+    ("collision.monster_island", "Un monstruo marino cambió de rumbo para evitar la isla"),
+    ("collision.eaten", "¡Se lo ha comido un monstruo marino!"),
+    ("collision.hq_eaten", "¡Un monstruo marino se comió su cuartel general!"),
+    ("collision.ship_eaten_moving", "¡Un monstruo en movimiento se comió un barco!"),
+    ("collision.monster_mined", "¡Una mina destruyó un monstruo marino!"),
+    ("collision.monster_fight", "¡¡Pelea de monstruos marinos!!"),
+    ("collision.tie", "¡Empate!"),
+    ("collision.one_dies", "¡¡Y uno muere!!"),
+    ("collision.edge", "{0} cambió de rumbo para no salir de la zona."),
+    ("attack.firing_bearing", "Barco enemigo {0} disparando..."),
+    ("attack.firing", "Barco enemigo en {0} disparando..."),
+    ("attack.threat", "Amenaza: {0}"),
+    ("attack.depth_charges", "¡Cargas de profundidad por {0}, {1}!"),
+    ("attack.no_damage", "Ningún daño de importancia, {0}."),
+    ("attack.light", "¡Daños leves y superficiales, {0}!"),
+    ("attack.moderate", "¡¡Daños moderados, hacen falta reparaciones, {0}!!"),
+    ("attack.heavy", "¡¡Daños graves!! ¡¡Reparaciones inmediatas, {0}!!"),
+    ("attack.critical",
+     "¡¡¡Daños críticos!!!  ¡¡¡Necesitamos ayuda!!!\n\
+      Envíe 'HELP' en clave.  Aquí está la clave: "),
+    ("attack.garbled", "La clave llega ilegible."),
+    ("turn_summary",
+     "Turno {0}.  Profundidad {1}, energía {2}, combustible {3}, {4} torpedos, {5} misiles, {6} \
+      tripulantes."),
+    ("under_repair", "En reparación: {0}."),
+    ("undo.nothing", "No queda nada que deshacer, {0}."),
+    ("undo.real_war", "En una guerra de verdad no se puede deshacer, {0}."),
+    ("crew_gives_up", "La tripulación se cansa de esperar órdenes sensatas, {0}."),
+    ("pile_dead",
+     "¡¡La pila atómica se ha apagado!! El submarino se hunde, la tripulación se asfixia."),
+    ("sub_lost", "El submarino de {0} se ha hundido con toda la tripulación."),
+    ("waiting_for", "Esperando las órdenes de {0}."),
+    ("undo.back", "De vuelta al comienzo del turno {0}."),
+    ("port", "babor"),
+    ("starboard", "estribor"),
+    ("system.engines", "Motores"),
+    ("system.sonar", "Sonar"),
+    ("system.torpedos", "Torpedos"),
+    ("system.missiles", "Misiles"),
+    ("system.manuevering", "Maniobra"),
+    ("system.computers", "Ordenadores"),
+    ("system.resupply", "Carga"),
+    ("system.sabotage", "Sabotaje"),
+    ("system.converter", "Convertidor"),
+    ("order.none", "No se dieron órdenes"),
+    ("order.no_such_command", "No existe la orden {0}"),
+    ("order.unknown", "Orden desconocida '{0}'"),
+    ("order.ambiguous", "'{0}' puede ser {1}"),
+    ("or", " o "),
+    ("order.not_a", "'{0}' no vale como {1}"),
+    ("order.quantity", "cantidad"),
+    ("order.depth", "profundidad"),
+    ("order.men", "número de hombres"),
+    ("order.turns", "número de turnos"),
+    ("order.not_a_direction", "'{0}' no es una dirección"),
+    ("order.conversion", "Convierta combustible o energía, no '{0}'"),
+    ("order.extra", "No está claro qué significa '{0}'"),
+    ("legend.own", "su submarino"),
+    ("legend.other", "submarino de otro jugador"),
+    ("legend.ship", "barco enemigo"),
+    ("legend.monster", "monstruo marino"),
+    ("legend.mine", "mina"),
+    ("legend.hq", "cuartel general"),
+    ("legend.island", "isla"),
+    ("noun.sub", "Submarino"),
+    ("nouns.sub", "submarinos"),
+    ("noun.island", "Isla"),
+    ("nouns.island", "islas"),
+    ("noun.ship", "Barco enemigo"),
+    ("nouns.ship", "barcos enemigos"),
+    ("noun.mine", "Mina"),
+    ("nouns.mine", "minas"),
+    ("noun.hq", "Cuartel general"),
+    ("nouns.hq", "cuarteles generales"),
+    ("noun.monster", "Monstruo marino"),
+    ("nouns.monster", "monstruos marinos"),
+    ("compass.n", "norte"),
+    ("compass.ne", "noreste"),
+    ("compass.e", "este"),
+    ("compass.se", "sureste"),
+    ("compass.s", "sur"),
+    ("compass.sw", "suroeste"),
+    ("compass.w", "oeste"),
+    ("compass.nw", "noroeste"),
+    ("bearing.here", "aquí mismo"),
+    ("bearing.cell", "a 1 casilla al {0}"),
+    ("bearing.cells", "a {0} casillas al {1}"),
+    ("sonar.nothing", "El sonar no detecta nada."),
+    ("sonar.contacts", "Contactos de sonar, los más cercanos primero:"),
+    ("sonar.contact", "{0} {1}."),
+    ("sonar.several", "{0} {1} {2}."),
+    ("sonar.far_islands", "{0} casillas más de isla, más lejos."),
+    ("overview", "Vista general, cada casilla es de {0}x{0}"),
+    ("sector", "Sector {0}-{1} al este, {2}-{3} al sur, de {4}x{5}"),
+    ("help.index",
+     "Escriba help seguido de una orden o un tema, p. ej. \"help torpedo\".\n\
+      Órdenes: navigate sonar torpedo missile manuever status\n\
+      \x20        resupply sabotage convert surrender undo log\n\
+      Temas:"),
+    ("help.navigate",
+     "NAVIGATE DIRECCIÓN ENERGÍA\n\
+      Avanza una casilla por cada {0} unidades de energía.  Hacen falta {1}\n\
+      tripulantes y los motores en marcha.  A {2} pies o menos el submarino va\n\
+      más lento.  Pasar más de {3} unidades por la pila de una vez supone un {4}%\n\
+      de probabilidad de que entre en estado supercrítico.  Chocar con un barco,\n\
+      una mina o el cuartel general hunde el submarino, y las islas lo detienen."),
+    ("help.sonar",
+     "SONAR\n\
+      Cartografía todo el océano, por {0} unidades de energía.  Hacen falta {1}\n\
+      tripulantes.  No acaba el turno.  Vea también: help chart"),
+    ("help.torpedo",
+     "TORPEDO DIRECCIÓN\n\
+      Lanza un torpedo en línea recta, por {0} unidades de energía.  Hacen falta\n\
+      {1} tripulantes.  Recorre de {2} a {3} casillas, {4} más por debajo de {5}\n\
+      pies, y alcanza lo primero que encuentre.  A las minas y a los monstruos no\n\
+      les hace nada.  Disparar a {6} pies o más puede hacer implosionar el\n\
+      submarino."),
+    ("help.missile",
+     "MISSILE DIRECCIÓN COMBUSTIBLE\n\
+      Vuela una casilla por cada {0} LBS. de combustible, y luego destruye todo\n\
+      lo que haya a una casilla de donde cae, su propio submarino y el cuartel\n\
+      general incluidos.  Hacen falta {1} tripulantes.  Lanzarlo a {2} pies o\n\
+      menos, o a {3} o más, puede hacer volar el submarino."),
+    ("help.manuever",
+     "MANUEVER PROFUNDIDAD  (o DIVE PROFUNDIDAD)\n\
+      Cambia de profundidad, con una unidad de energía por cada {0} pies.  Hacen\n\
+      falta {1} tripulantes.  El casco cede a {2} pies.  Vea también: help depth"),
+    ("help.status",
+     "STATUS\n\
+      Informa de los suministros, el estado de cada sistema y la última imagen\n\
+      del sonar.  Hacen falta {0} tripulantes.  No acaba el turno."),
+    ("help.resupply",
+     "RESUPPLY\n\
+      Atraca en el cuartel general, que debe estar a {0} casillas o menos, a {1}\n\
+      pies o menos.  {2} {3} de energía, {4} torpedos, {5} misiles,\n\
+      {6} LBS. de combustible y {7} tripulantes.  El cuartel general solo tiene\n\
+      unas cuantas cargas."),
+    ("help.sabotage",
+     "SABOTAGE HOMBRES\n\
+      Envía hombres a hundir barcos a {0} casillas o menos.  Hacen falta {1}\n\
+      tripulantes, y {2} deben quedarse a bordo.  Más hombres hunden más barcos,\n\
+      pero cada uno solo tiene un {3}% de probabilidad de volver, menos si hay\n\
+      monstruos cerca."),
+    ("help.convert",
+     "CONVERT FUEL|POWER CANTIDAD\n\
+      Convierte {0} LBS. de combustible en una unidad de energía, o una unidad\n\
+      de energía en {1} LBS. de combustible.  Hacen falta {2} tripulantes."),
+    ("help.surrender",
+     "SURRENDER\n\
+      Acaba la partida para su submarino.  No se recomienda."),
+    ("help.undo",
+     "UNDO\n\
+      En las partidas de práctica, vuelve al comienzo del turno anterior."),
+    ("help.log",
+     "LOG [TURNOS]\n\
+      Lee el cuaderno de bitácora: órdenes cumplidas y lo que gastaron,\n\
+      contactos de sonar, cargas de profundidad recibidas y barcos hundidos, de\n\
+      toda la partida o solo de los últimos turnos.  No acaba el turno.  El\n\
+      cuaderno se puede guardar al acabar la partida con --log FICHERO, en JSON\n\
+      si FICHERO acaba en .json."),
+    ("help.north", "El norte está en la parte de arriba de la carta."),
+    ("help.sonar_shows", "El sonar muestra:"),
+    ("help.chart",
+     "Los océanos grandes se muestran por secciones, con una vista general en\n\
+      la que cada casilla representa varias.\n\
+      Con --accessible, el sonar lee en voz alta la distancia y el rumbo de\n\
+      cada contacto, los más cercanos primero."),
+    ("help.enemies",
+     "Los barcos enemigos vagan por el océano, y los que están a {0} casillas o\n\
+      menos lanzan cargas de profundidad después de su turno.  Un barco que se\n\
+      mueve contra el submarino lo hunde.\n\
+      Los monstruos marinos se comen todo lo que encuentran, barcos incluidos.\n\
+      Pasar a {1} casillas o menos de uno le da un {2}% de probabilidad de\n\
+      atrapar al submarino.\n\
+      Las minas no se mueven.  Los barcos suelen evitarlas; los submarinos\n\
+      también deberían."),
+    ("help.damage",
+     "Las cargas de profundidad gastan energía y dañan sistemas.  Un sistema con\n\
+      estado negativo queda fuera de servicio hasta que se repara:"),
+    ("help.repairs",
+     "Las reparaciones se hacen cada turno, el doble de rápido a {0} pies o\n\
+      menos, o a más de {1} pies."),
+    ("help.depth",
+     "{0} pies o menos: más lento, los torpedos tienen menos alcance, los misiles\n\
+      \x20   son arriesgados, las reparaciones van más rápido y el cuartel general\n\
+      \x20   puede reabastecer el submarino.\n\
+      Más de {1} pies: los torpedos recorren {2} casillas más.\n\
+      {3} pies o más: los torpedos y los misiles pueden destruir el submarino.\n\
+      {4} pies: aplastado."),
+    ("help.orders",
+     "Las órdenes se pueden escribir enteras, \"nav 9 300\", o en parte, \"nav\", y\n\
+      se preguntará por lo que falte.  Pulse intro o q ante una pregunta para\n\
+      anular la orden.\n\
+      Varias órdenes separadas por ; se cumplen por turnos, \"dive 100; sonar;\n\
+      torp 6\".  Se retienen si el submarino recibe fuego, algo se acerca o queda\n\
+      poca energía.\n\
+      \"macro NOMBRE ÓRDENES\" guarda órdenes para darlas otra vez con NOMBRE.\n\
+      \"macro\" las muestra, y \"macro NOMBRE\" olvida una."),
+    ("help.missions",
+     "La misión de siempre es hundir todos los barcos enemigos.  Con --mission,\n\
+      hay que cumplir todos los objetivos dados para ganar:\n\
+      \x20   sink=N      hundir N barcos        flagship    hundir el buque insignia\n\
+      \x20   waypoint    llegar a un punto      survive=N   seguir a flote N turnos\n\
+      y cualquier fracaso dado acaba la partida en derrota:\n\
+      \x20   turns=N     no cumplir los objetivos en N turnos\n\
+      \x20   hq          perder el cuartel general\n\
+      \x20   undetected  un barco se pone al costado, o a {0} casillas o menos\n\
+      \x20               con el submarino a {1} pies o menos\n\
+      Un informe de estado muestra cómo va cada parte de la misión."),
+    ("help.sets_stocks", "Repone exactamente"),
+    ("help.tops_up", "Repone hasta"),
+    ("topic.directions", "hacia dónde queda cada cosa"),
+    ("topic.chart", "qué significan los símbolos del sonar"),
+    ("topic.enemies", "barcos, monstruos y minas"),
+    ("topic.damage", "cargas de profundidad y reparaciones"),
+    ("topic.depth", "qué cambia con la profundidad"),
+    ("topic.orders", "escribir órdenes, colas y macros"),
+    ("topic.missions", "objetivos, y qué hace fracasar una misión"),
+    ("cause.mine", "volado por una mina"),
+    ("cause.monster", "devorado por un monstruo marino"),
+    ("cause.rammed", "hundido en una colisión"),
+    ("cause.supercritical", "perdido cuando la pila entró en estado supercrítico"),
+    ("cause.power_exhausted", "hundido al quedarse sin energía"),
+    ("cause.retaliation", "hundido al agotar la energía las cargas de profundidad"),
+    ("cause.crushed", "aplastado por las profundidades"),
+    ("cause.misfire", "destruido por sus propias armas"),
+    ("cause.friendly_fire", "hundido por otro submarino"),
+    ("cause.surrendered", "rendido"),
+    ("weapon.torpedo", "torpedo"),
+    ("weapon.missile", "misil"),
+    ("weapon.sabotage", "sabotaje"),
+    ("weapon.ramming", "embestida"),
+    ("debrief.title", "INFORME DE LA ACCIÓN"),
+    ("ending.all_sunk", "todos los barcos enemigos hundidos"),
+    ("ending.accomplished", "la misión cumplida"),
+    ("ending.out_of_time", "el tiempo agotado"),
+    ("ending.hq_lost", "el cuartel general destruido"),
+    ("ending.detected", "los submarinos descubiertos por el enemigo"),
+    ("ending.all_lost", "ningún submarino a flote"),
+    ("ending.called_off", "la partida suspendida"),
+    ("debrief.lasted", "La acción duró {0} turnos.  Final: {1}."),
+    ("debrief.wrecks",
+     "El mar se llevó {0} barcos enemigos: {1} por minas, {2} por monstruos marinos."),
+    ("debrief.survived", "    Sobrevivió los {0} turnos."),
+    ("debrief.lost", "    Fuera de combate: {0}."),
+    ("debrief.sunk_by", "{0} por {1}"),
+    ("none", "ninguno"),
+    ("lbs", "{0} LBS."),
+    ("debrief.ships_sunk", "Barcos hundidos:"),
+    ("debrief.torpedos", "Torpedos lanzados:"),
+    ("debrief.missiles", "Misiles lanzados:"),
+    ("debrief.fuel", "Combustible usado:"),
+    ("debrief.crew_lost", "Bajas:"),
+    ("debrief.resupplies", "Reabastecimientos:"),
+    ("debrief.damage", "Daños recibidos:"),
+    ("score.ships_sunk", "barcos hundidos"),
+    ("score.victory", "victoria"),
+    ("score.speed", "rapidez"),
+    ("score.crew", "tripulación a bordo"),
+    ("score.supplies", "suministros restantes"),
+    ("score.hq_lost", "cuartel general perdido"),
+    ("score.surrender", "rendición"),
+    ("score.difficulty", "dificultad"),
+    ("score.total", "Puntuación de {0}: {1}"),
+    ("score.table",
+     "    MEJORES PUNTUACIONES\n\
+      \x20   PUESTO CAPITÁN          PUNTOS  PARTIDAS"),
+    ("rank.deck_scrubber", "Friegacubiertas"),
+    ("rank.ensign", "Alférez"),
+    ("rank.lieutenant", "Teniente"),
+    ("rank.lieutenant_commander", "Capitán de corbeta"),
+    ("rank.commander", "Capitán de fragata"),
+    ("rank.captain", "Capitán de navío"),
+    ("rank.commodore", "Comodoro"),
+    ("rank.admiral", "Almirante"),
+    ("medal.navy_cross", "Cruz Naval"),
+    ("medal.navy_cross.for", "limpiar el océano de barcos enemigos"),
+    ("medal.silver_star", "Estrella de Plata"),
+    ("medal.silver_star.for", "hundir 10 barcos en una sola acción"),
+    ("medal.torpedo_ace", "As del Torpedo"),
+    ("medal.torpedo_ace.for", "hundir 5 barcos con torpedos"),
+    ("medal.saboteurs_star", "Estrella del Saboteador"),
+    ("medal.saboteurs_star.for", "hundir 3 barcos con sabotaje"),
+    ("medal.swift_victory", "Victoria Fulminante"),
+    ("medal.swift_victory.for", "ganar en 30 turnos o menos"),
+    ("medal.purple_heart", "Corazón Púrpura"),
+    ("medal.purple_heart.for", "hundirse luchando"),
+    ("career.awarded", "{0} recibe la {1} por {2}."),
+    ("career.promoted", "¡{0} asciende a {1}!"),
+    ("career.demoted", "¡¡{0} es degradado al rango de {1}!!"),
+    ("career.totals",
+     "    {0} acciones, {1} victorias, {2} barcos hundidos, {3} puntos (mejor {4})"),
+    ("career.next_rank", "    Ascenso a {0} con {1} victorias y {2} puntos."),
+    ("career.recent", "    Acciones recientes:"),
+    ("career.victory", "victoria"),
+    ("career.defeat", "derrota"),
+    ("career.action", "        {0} {1} turnos {2} barcos {3} puntos"),
+    ("stock.power", "energía"),
+    ("stock.fuel", "combustible"),
+    ("stock.torpedos", "torpedos"),
+    ("stock.missiles", "misiles"),
+    ("stock.crew", "tripulación"),
+    ("severity.no", "ningún"),
+    ("severity.light", "leve"),
+    ("severity.moderate", "moderado"),
+    ("severity.heavy", "grave"),
+    ("severity.critical", "crítico"),
+    ("log.sunk", ", {0} barcos hundidos"),
+    ("log.contacts", "sonar: {0} barcos, {1} monstruos, {2} minas, {3} otros submarinos"),
+    ("log.hit", "cargas de profundidad, daño {0}"),
+    ("log.power", ", energía -{0}"),
+    ("log.systems", ", alcanzados {0}"),
+    ("log.lost", "submarino perdido: {0}"),
+    ("log.turn", "Turno {0}"),
+    ("log.empty", "El diario aún está vacío."),
+    ("log.of", "Diario de {0}"),
+    ("macro.none", "Aún no hay macros.  Pruebe: macro attack dive 100; sonar; torpedo 6"),
+    ("macro.command", "'{0}' ya es una orden."),
+    ("macro.forgotten", "Macro {0} olvidada."),
+    ("macro.saved", "Macro {0} guardada."),
+    ("alert.attack", "Nos están atacando"),
+    ("alert.power", "La energía ha bajado a {0}"),
+    ("alert.contact", "Contacto de sonar en {0}"),
+    ("alert.holding", "¡{0}!  Se retienen las {1} órdenes restantes, {2}."),
+    ("queued", "{0}, y quedan {1} más."),
+    ("difficulty.unknown", "'{0}' no es ninguno de {1} ni custom"),
+    ("difficulty.expected", "Se esperaba NOMBRE=VALOR, no '{0}'"),
+    ("difficulty.range", "{0} necesita un número o un intervalo como 10-20, no '{1}'"),
+    ("difficulty.number", "{0} necesita un número mayor que 0, no '{1}'"),
+    ("difficulty.count", "{0} necesita un número entero, como 2, no '{1}'"),
+    ("difficulty.no_ships", "Tiene que haber al menos un barco"),
+    ("difficulty.key",
+     "No se puede cambiar '{0}'.  Pruebe ships, mines, monsters, threat, repair, resupplies o \
+      reward"),
+    ("difficulty.describe",
+     "{0}: {1}-{2} barcos, {3}-{4} minas, {5} monstruos, amenaza x{6}, reparaciones x{7}, {8} \
+      reabastecimientos, puntuación x{9}"),
+    ("difficulty", "Dificultad {0}"),
+    ("house_rules", "Reglas de la casa: {0}"),
+    ("personal_best", "¡Un récord personal, {0}!"),
+    ("save.scores", "No se pudieron guardar las puntuaciones en {0}: {1}"),
+    ("save.log", "No se pudo guardar el diario en {0}: {1}"),
+    ("save.careers", "No se pudieron guardar las carreras en {0}: {1}"),
+    ("session",
+     "    ESTA SESIÓN, {0} PARTIDAS\n\
+      \x20   CAPITÁN           VICT. BARCOS   PUNTUACIÓN"),
+    ("again",
+     "¿Otra partida?  1: partida nueva,  2: repetir esta (semilla {0}),\n\
+      \x20               3: otro océano,  intro para terminar"),
+    ("again.choice", "¿Su elección"),
+    ("again.retry", "Elija 1, 2 o 3, o pulse intro para terminar."),
+    ("scenario.size", "¿Tamaño del océano ({0}x{1})"),
+    ("scenario.size.retry", "El ancho y el alto van de {0} a {1}, p. ej. 30x20."),
+    ("scenario.wrap.on", "¿Dar la vuelta por los bordes (S/n)"),
+    ("scenario.wrap.off", "¿Dar la vuelta por los bordes (s/N)"),
+    ("scenario.difficulty", "¿Dificultad ({0})"),
+    ("net.lost", "Se perdió el contacto con {0}.  Partida abandonada."),
+    ("net.listen", "No se puede escuchar en el puerto {0}: {1}"),
+    ("net.waiting", "Esperando a que el capitán n.º {0} se una en el puerto {1}..."),
+    ("net.joined", "El capitán n.º {0} se unió desde {1}."),
+    ("net.failed", "Conexión fallida: {0}"),
+    ("net.unreachable", "No se puede contactar con {0}: {1}"),
+    ("net.connected", "Conectado a {0}.  Esperando a que empiece la partida..."),
+    ("net.closed", "Conexión cerrada."),
+    ("missing.direction", "Falta la dirección"),
+    ("missing.power", "Falta la energía"),
+    ("missing.fuel", "Falta el combustible"),
+    ("missing.depth", "Falta la profundidad"),
+    ("missing.men", "Faltan los hombres"),
+    ("missing.conversion", "Falta fuel o power"),
+    ("missing.amount", "Falta la cantidad"),
+    ("mission.number", "{0} necesita un número mayor que 0, p. ej. {0}=10"),
+    ("mission.unknown",
+     "'{0}' no forma parte de ninguna misión.  Pruebe all, sink=N, flagship, waypoint, survive=N, \
+      turns=N, hq o undetected"),
+    ("rules.value", "{0} no puede valer '{1}'"),
+    ("rules.unknown", "no hay ninguna regla llamada '{0}'"),
+    ("rules.expected", "se esperaba NOMBRE = VALOR, no '{0}'"),
+    ("rules.line", "{0}, línea {1}: {2}"),
+    ("rules.chance", "{0} es una probabilidad, de 0 a 1"),
+    ("rules.negative", "{0} no puede ser menor que 0"),
+    ("rules.above_zero", "{0} tiene que ser mayor que 0"),
+    ("rules.depths",
+     "las profundidades deben cumplir 0 < shallow_depth < deep_depth < crush_depth"),
+    ("rules.sabotage", "los saboteadores tienen que poder salir: skeleton_crew < sabotage_crew"),
+    ("rules.torpedos",
+     "los torpedos tienen que recorrer al menos una casilla: 0 <= torpedo_spread < torpedo_range"),
+    ("usage",
+     "Uso: rust_seawar [--size ANCHOxALTO] [--wrap] [--players N] [--ai N] [--versus]\n\
+      \x20                [--host PUERTO | --join DIRECCIÓN] [--seed N] [--bot]\n\
+      \x20                [--practice] [--undo N] [--lang CÓDIGO] [--accessible]\n\
+      \x20                [--log FICHERO] [--scores FICHERO | --no-scores]\n\
+      \x20                [--career | --career-file FICHERO] [--difficulty PERFIL]\n\
+      \x20                [--rules FICHERO] [--show-rules] [--classic]\n\
+      \x20                [--mission MISIÓN] [--turns N] [--sink N]"),
+    ("usage.size",
+     "  --size     Tamaño del océano, de {0} a {1} casillas por lado (20x20 si no)\n\
+      \x20            Barcos, minas y monstruos aumentan con la superficie"),
+    ("usage.wrap", "  --wrap     El océano da la vuelta por los bordes"),
+    ("usage.players", "  --players  Submarinos que se turnan en este terminal, de 1 a {0}"),
+    ("usage.ai", "  --ai       Cuántos de esos submarinos manda el ordenador"),
+    ("usage.versus", "  --versus   Los capitanes compiten por hundir más barcos"),
+    ("usage.host", "  --host     Esperar a que los demás capitanes se unan en PUERTO"),
+    ("usage.join", "  --join     Unirse a la partida en DIRECCIÓN, p. ej. localhost:7070"),
+    ("usage.seed", "  --seed     Repetir la partida que empezó con la semilla N"),
+    ("usage.bot",
+     "  --bot      Un programa toma el timón, con el protocolo de bots por stdin/stdout"),
+    ("usage.practice",
+     "  --practice Partida de práctica, en la que se pueden deshacer los últimos {0} turnos"),
+    ("usage.undo",
+     "  --undo     Partida de práctica, en la que se pueden deshacer los últimos N turnos"),
+    ("usage.lang", "  --lang     Idioma de juego, en o es (según LANG si no)"),
+    ("usage.log",
+     "  --log      Guardar el diario en FICHERO al final, en JSON si acaba en .json\n\
+      \x20            Cada partida siguiente va a su fichero: FICHERO-2, FICHERO-3... antes\n\
+      \x20            de la extensión"),
+    ("usage.scores", "  --scores   Guardar las puntuaciones en FICHERO (~/.seawar_scores si no)"),
+    ("usage.no_scores", "  --no-scores  No guardar puntuaciones"),
+    ("usage.career", "  --career   Los capitanes conservan rango y medallas de una partida a otra"),
+    ("usage.career_file",
+     "  --career-file  Guardar las carreras en FICHERO (~/.seawar_career si no)"),
+    ("usage.difficulty",
+     "  --difficulty  cadet, lieutenant, commander (por defecto), captain o admiral,\n\
+      \x20            o custom, con cambios en ships, mines, monsters, threat, repair,\n\
+      \x20            resupplies o reward, p. ej. captain,monsters=2,repair=1.5"),
+    ("usage.mission",
+     "  --mission  Qué hace falta para ganar, y qué hace perder, como lista de:\n\
+      \x20              all         hundir todos los barcos enemigos (salvo otros objetivos)\n\
+      \x20              sink=N      hundir N barcos enemigos\n\
+      \x20              flagship    hundir el buque insignia enemigo\n\
+      \x20              waypoint    llegar a un punto de encuentro\n\
+      \x20              survive=N   seguir a flote N turnos\n\
+      \x20              turns=N     perder si no se cumplen los objetivos en N turnos\n\
+      \x20              hq          perder si destruyen el cuartel general\n\
+      \x20              undetected  perder si un barco enemigo ve un submarino: uno al lado,\n\
+      \x20                          o uno a su alcance con el submarino cerca de la superficie\n\
+      \x20            p. ej. --mission sink=10,turns=30,hq"),
+    ("usage.turns", "  --turns    Igual que --mission turns=N"),
+    ("usage.sink", "  --sink     Igual que --mission sink=N"),
+    ("usage.rules", "  --rules    Jugar con las reglas de la casa de FICHERO"),
+    ("usage.classic",
+     "  --classic  Jugar con las reglas del juego original en BASIC donde difieran"),
+    ("usage.show_rules",
+     "  --show-rules  Mostrar las reglas en juego, como fichero de reglas de partida"),
+    ("usage.accessible",
+     "  --accessible  Describir el sonar con palabras, para lectores de pantalla, sin animación"),
+    ("rule.navigate_crew", "Tripulantes necesarios a bordo para navegar."),
+    ("rule.sonar_crew", "Tripulantes necesarios a bordo para usar el sonar."),
+    ("rule.torpedo_crew", "Tripulantes necesarios a bordo para lanzar un torpedo."),
+    ("rule.missile_crew", "Tripulantes necesarios a bordo para lanzar un misil."),
+    ("rule.manuever_crew", "Tripulantes necesarios a bordo para sumergirse o subir."),
+    ("rule.status_crew", "Tripulantes necesarios a bordo para dar un informe de estado."),
+    ("rule.sabotage_crew", "Tripulantes necesarios a bordo para enviar saboteadores."),
+    ("rule.convert_crew", "Tripulantes necesarios a bordo para convertir combustible o energía."),
+    ("rule.skeleton_crew", "Hombres que deben quedarse a bordo mientras los demás sabotean."),
+    ("rule.power_per_move", "Energía para avanzar una casilla."),
+    ("rule.sonar_power", "Energía de un barrido de sonar."),
+    ("rule.torpedo_power", "Energía para lanzar un torpedo."),
+    ("rule.feet_per_power", "Pies que se baja o se sube por unidad de energía."),
+    ("rule.fuel_per_cell", "Libras de combustible para llevar un misil una casilla."),
+    ("rule.fuel_per_power",
+     "Libras de combustible que da una unidad de energía, o que cuesta una."),
+    ("rule.safe_power",
+     "Pasar más energía que esta por la pila puede ponerla en estado supercrítico."),
+    ("rule.pile_safety", "Probabilidad de que la pila aguante si se pasa de safe_power."),
+    ("rule.shallow_depth",
+     "A esta profundidad o menos, el submarino va lento y puede atracar en el cuartel general."),
+    ("rule.deep_depth", "A esta profundidad o más, torpedos y misiles son arriesgados."),
+    ("rule.crush_depth", "El casco cede a esta profundidad."),
+    ("rule.torpedo_range", "Máximo que recorre un torpedo."),
+    ("rule.torpedo_spread", "Máximo que un torpedo se queda corto, al azar."),
+    ("rule.deep_torpedo_bonus",
+     "Alcance extra de los torpedos lanzados por debajo de shallow_depth."),
+    ("rule.deep_torpedo_safety",
+     "Probabilidad de que un torpedo lanzado a deep_depth o más no haga implosionar el submarino."),
+    ("rule.deep_missile_safety",
+     "Probabilidad de que un misil lanzado a deep_depth o más no haga volar el submarino."),
+    ("rule.docking_range", "Distancia al cuartel general a la que un submarino puede atracar."),
+    ("rule.resupply_power", "Energía tras un reabastecimiento."),
+    ("rule.resupply_torpedos", "Torpedos tras un reabastecimiento."),
+    ("rule.resupply_missiles", "Misiles tras un reabastecimiento."),
+    ("rule.resupply_fuel", "Combustible tras un reabastecimiento."),
+    ("rule.resupply_crew", "Tripulantes tras un reabastecimiento."),
+    ("rule.sabotage_range", "Distancia a la que los saboteadores tienen que acercarse a un barco."),
+    ("rule.saboteur_survival", "Probabilidad de que cada saboteador vuelva."),
+    ("rule.saboteur_eaten",
+     "Probabilidad de que un monstruo marino cercano devore a cada saboteador a la vuelta."),
+    ("rule.monster_reach",
+     "Distancia a la que un monstruo marino puede devorar un submarino que pasa."),
+    ("rule.monster_bite",
+     "Probabilidad de que un monstruo marino a su alcance devore el submarino."),
+    ("rule.monster_escape",
+     "Probabilidad de que el submarino escape de un monstruo marino con el que choca."),
+    ("rule.ship_mine_avoidance",
+     "Probabilidad de que un barco enemigo que va hacia una mina la esquive."),
+    ("rule.ship_range", "Distancia a la que los barcos enemigos lanzan cargas de profundidad."),
+    ("rule.charge_miss",
+     "Probabilidad de que las cargas de profundidad dentro del primer nivel de amenaza no hagan \
+      daño."),
+    ("rule.light_hit_chance",
+     "Probabilidad de que las cargas de profundidad dentro del segundo nivel de amenaza solo \
+      hagan daños leves."),
+    ("rule.light_hit_power", "Energía que se pierde con un impacto leve."),
+    ("rule.light_hit_spread",
+     "Máximo de energía extra que se pierde con un impacto leve, al azar."),
+    ("rule.light_hit_systems", "Sistemas que daña un impacto leve."),
+    ("rule.light_hit_damage", "Daño máximo de un impacto leve a cada sistema."),
+    ("rule.moderate_hit_chance",
+     "Probabilidad de que las cargas de profundidad dentro del tercer nivel de amenaza solo hagan \
+      daños moderados."),
+    ("rule.moderate_hit_power", "Energía que se pierde con un impacto moderado."),
+    ("rule.moderate_hit_spread",
+     "Máximo de energía extra que se pierde con un impacto moderado, al azar."),
+    ("rule.moderate_hit_systems", "Sistemas que daña un impacto moderado."),
+    ("rule.moderate_hit_damage", "Daño máximo de un impacto moderado a cada sistema."),
+    ("rule.heavy_hit_chance",
+     "Probabilidad de que las cargas de profundidad dentro del cuarto nivel de amenaza solo hagan \
+      daños graves."),
+    ("rule.heavy_hit_power", "Energía que se pierde con un impacto grave."),
+    ("rule.heavy_hit_spread",
+     "Máximo de energía extra que se pierde con un impacto grave, al azar."),
+    ("rule.heavy_hit_systems", "Sistemas que daña un impacto grave."),
+    ("rule.heavy_hit_damage", "Daño máximo de un impacto grave a cada sistema."),
+    ("rule.critical_hit_power", "Energía que se pierde con un impacto crítico."),
+    ("rule.critical_hit_spread",
+     "Máximo de energía extra que se pierde con un impacto crítico, al azar."),
+    ("rule.critical_hit_systems", "Sistemas que daña un impacto crítico."),
+    ("rule.critical_hit_damage", "Daño máximo de un impacto crítico a cada sistema."),
+    ("rule.monster_standoff",
+     "Probabilidad de que dos monstruos marinos que chocan sigan los dos su camino."),
+    ("rule.classic", "Jugar como el juego original en BASIC, donde este difiere."),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_are_filled_in_once() {
+        set_language(Language::English);
+        assert_eq!(tr!("skeleton_crew", 4, "Nemo"), "You must leave at least 4 men on board, Nemo");
+        assert_eq!(tr!("prompt.fuel.retry", 30, "Nemo"),
+                   "You have 30 LBS. left, Nemo.  Enter 1 to 30, or press return to cancel.");
+        // A name that looks like a placeholder is left alone.
+        assert_eq!(tr!("skeleton_crew", "{1}", "{0}"), "You must leave at least {1} men on board, {0}");
+        // Placeholders without an argument stay as they are.
+        assert_eq!(tr!("skeleton_crew", 4), "You must leave at least 4 men on board, {1}");
+    }
+
+    #[test]
+    fn missing_messages_fall_back() {
+        static FIRST: [(&str, &str); 1] = [("both", "first")];
+        static SECOND: [(&str, &str); 2] = [("both", "second"), ("second", "only second")];
+        assert_eq!(search(&[&FIRST, &SECOND], "both"), Some("first"));
+        assert_eq!(search(&[&FIRST, &SECOND], "second"), Some("only second"));
+        assert_eq!(search(&[&FIRST, &SECOND], "neither"), None);
+
+        set_language(Language::Spanish);
+        assert_eq!(lookup("skeleton_crew"), "Deben quedar al menos {0} hombres a bordo, {1}");
+        assert_eq!(lookup("no.such.message"), "no.such.message");
+        set_language(Language::English);
+    }
+
+    #[test]
+    fn language_codes() {
+        assert_eq!(Language::from_code("es"), Some(Language::Spanish));
+        assert_eq!(Language::from_code("ES"), Some(Language::Spanish));
+        assert_eq!(Language::from_code("es_ES.UTF-8"), Some(Language::Spanish));
+        assert_eq!(Language::from_code("en-GB"), Some(Language::English));
+        assert_eq!(Language::from_code("fr_FR"), None);
+        assert_eq!(Language::from_code(""), None);
+    }
+}
@@ -0,0 +1,422 @@
+// Rendering of the ocean for sonar and status displays.
+//
+// The whole ocean no longer fits on a terminal once the board gets large,
+// so sonar draws a window (viewport) centred on the player, which can be
+// panned around, and an overview (minimap) which squeezes several ocean
+// cells into each character cell.
+
+use super::{ocean, EType, Position};
+
+// Largest area drawn cell-for-cell.  This is the size of the original board.
+pub const VIEW_WIDTH: usize = 20;
+pub const VIEW_HEIGHT: usize = 20;
+
+// Size of the overview shown with the status report.
+pub const STATUS_OVERVIEW: usize = 10;
+
+fn glyph(etype: &Option<EType>, own: usize) -> &'static str {
+    match *etype {
+        None => "  ",
+        Some(EType::Player(id)) if id == own => "==",
+        Some(EType::Player(_)) => "()",
+        Some(EType::Island) => "%%",
+        Some(EType::Ship) => "<>",
+        Some(EType::Mine) => " *",
+        Some(EType::HQ) => "HQ",
+        Some(EType::Monster) => "SS",
+    }
+}
+
+// When several things share an overview cell, show the most interesting.
+fn priority(etype: &Option<EType>) -> u8 {
+    match *etype {
+        None => 0,
+        Some(EType::Island) => 1,
+        Some(EType::Mine) => 2,
+        Some(EType::HQ) => 3,
+        Some(EType::Monster) => 4,
+        Some(EType::Ship) => 5,
+        Some(EType::Player(_)) => 6,
+    }
+}
+
+/// What the symbols on a chart stand for.
+pub fn draw_legend(own: usize) {
+    let legend = [
+        (Some(EType::Player(own)), "legend.own"),
+        (Some(EType::Player(own + 1)), "legend.other"),
+        (Some(EType::Ship), "legend.ship"),
+        (Some(EType::Monster), "legend.monster"),
+        (Some(EType::Mine), "legend.mine"),
+        (Some(EType::HQ), "legend.hq"),
+        (Some(EType::Island), "legend.island"),
+    ];
+    for (etype, meaning) in legend.iter() {
+        outln!("    {}  {}", glyph(etype, own), tr!(meaning));
+    }
+}
+
+/// What contacts are called when they're read out rather than drawn, one
+/// and several.
+pub fn nouns(etype: &EType) -> (String, String) {
+    let (one, many) = match *etype {
+        EType::Player(_) => ("noun.sub", "nouns.sub"),
+        EType::Island => ("noun.island", "nouns.island"),
+        EType::Ship => ("noun.ship", "nouns.ship"),
+        EType::Mine => ("noun.mine", "nouns.mine"),
+        EType::HQ => ("noun.hq", "nouns.hq"),
+        EType::Monster => ("noun.monster", "nouns.monster"),
+    };
+    (tr!(one), tr!(many))
+}
+
+/// How far, and which way, to is from from, e.g. "3 cells northeast".
+pub fn bearing(from: &Position, to: &Position) -> String {
+    let (dx, dy) = from.heading(to);
+    let distance = dx.abs().max(dy.abs());
+    // Close enough to a diagonal counts as one.
+    let ns = if dy.abs() * 2 < dx.abs() { "" } else if dy < 0 { "n" } else { "s" };
+    let ew = if dx.abs() * 2 < dy.abs() { "" } else if dx < 0 { "w" } else { "e" };
+    let way = tr!(&format!("compass.{}{}", ns, ew));
+    match distance {
+        0 => tr!("bearing.here"),
+        1 => tr!("bearing.cell", way),
+        _ => tr!("bearing.cells", distance, way),
+    }
+}
+
+// Islands further off than this are only counted.
+const ISLAND_NOTICE: i32 = 2;
+
+/// Read out the chart as seen from pos, nearest contacts first.
+pub fn describe(chart: &Chart, pos: &Position) {
+    let mut contacts: Vec<_> = chart.contacts().into_iter()
+        .filter(|c| c.1 != EType::Player(chart.own))
+        .map(|(p, etype)| {
+            let (dx, dy) = pos.heading(&p);
+            (dx.abs().max(dy.abs()), p, etype)
+        })
+        .collect();
+    contacts.sort_by_key(|c| c.0);
+    let far_islands = contacts.iter()
+        .filter(|c| c.2 == EType::Island && c.0 > ISLAND_NOTICE)
+        .count();
+    contacts.retain(|c| c.2 != EType::Island || c.0 <= ISLAND_NOTICE);
+    if contacts.is_empty() && far_islands == 0 {
+        outln!("{}", tr!("sonar.nothing"));
+        return;
+    }
+    // Several of a kind in the same place are read out together.
+    let mut sentences: Vec<(EType, String, usize)> = Vec::new();
+    for (_, p, etype) in contacts {
+        let bearing = bearing(pos, &p);
+        match sentences.iter_mut().find(|s| s.0 == etype && s.1 == bearing) {
+            Some(s) => s.2 += 1,
+            None => sentences.push((etype, bearing, 1)),
+        }
+    }
+    outln!("{}", tr!("sonar.contacts"));
+    for (etype, bearing, count) in sentences {
+        let (one, many) = nouns(&etype);
+        if count == 1 {
+            outln!("{}", tr!("sonar.contact", one, bearing));
+        } else {
+            outln!("{}", tr!("sonar.several", count, many, bearing));
+        }
+    }
+    if far_islands > 0 {
+        outln!("{}", tr!("sonar.far_islands", far_islands));
+    }
+}
+
+/// A picture of the whole ocean, as seen by sonar from player own's sub.
+#[derive(Clone, Debug)]
+pub struct Chart {
+    own: usize,
+    width: usize,
+    height: usize,
+    wrap: bool,
+    cells: Vec<Option<EType>>,
+}
+
+impl Chart {
+    pub fn new(own: usize) -> Chart {
+        let o = ocean();
+        Chart {
+            own,
+            width: o.width,
+            height: o.height,
+            wrap: o.wrap,
+            cells: vec![None; o.width * o.height],
+        }
+    }
+
+    pub fn mark(&mut self, pos: &Position, etype: EType) {
+        self.cells[pos.y * self.width + pos.x] = Some(etype);
+    }
+
+    fn at(&self, x: usize, y: usize) -> &Option<EType> {
+        &self.cells[y * self.width + x]
+    }
+
+    pub fn contact(&self, pos: &Position) -> Option<EType> {
+        self.at(pos.x, pos.y).clone()
+    }
+
+    // Everything sonar picked up, including our own sub.
+    pub fn contacts(&self) -> Vec<(Position, EType)> {
+        let mut contacts = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if let Some(ref etype) = *self.at(x, y) {
+                    contacts.push((Position {x, y}, etype.clone()));
+                }
+            }
+        }
+        contacts
+    }
+
+    pub fn fits_view(&self) -> bool {
+        self.width <= VIEW_WIDTH && self.height <= VIEW_HEIGHT
+    }
+
+    // Whether there's anything to gain by panning.  A wrapped ocean can
+    // always be panned, so the view can be brought round to any cell.
+    pub fn pannable(&self) -> bool {
+        self.wrap || !self.fits_view()
+    }
+}
+
+/// The part of the chart drawn cell-for-cell.
+pub struct Viewport {
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Viewport {
+    // Centre the view on pos.  Unless the ocean wraps around, don't show
+    // anything past the edges.
+    pub fn centred(chart: &Chart, pos: &Position) -> Viewport {
+        let width = VIEW_WIDTH.min(chart.width);
+        let height = VIEW_HEIGHT.min(chart.height);
+        let mut view = Viewport { left: 0, top: 0, width, height };
+        view.move_to(chart,
+                      pos.x as isize - (width / 2) as isize,
+                      pos.y as isize - (height / 2) as isize);
+        view
+    }
+
+    fn move_to(&mut self, chart: &Chart, left: isize, top: isize) {
+        if chart.wrap {
+            self.left = left.rem_euclid(chart.width as isize) as usize;
+            self.top = top.rem_euclid(chart.height as isize) as usize;
+            return;
+        }
+        let max_left = (chart.width - self.width) as isize;
+        let max_top = (chart.height - self.height) as isize;
+        self.left = left.max(0).min(max_left) as usize;
+        self.top = top.max(0).min(max_top) as usize;
+    }
+
+    // Pan half a view in the given direction.
+    pub fn pan(&mut self, chart: &Chart, dx: i8, dy: i8) {
+        let left = self.left as isize + dx as isize * (self.width / 2) as isize;
+        let top = self.top as isize + dy as isize * (self.height / 2) as isize;
+        self.move_to(chart, left, top);
+    }
+
+    pub fn draw(&self, chart: &Chart) {
+        if self.left != 0 || self.top != 0 || !chart.fits_view() {
+            outln!("{}", tr!("sector",
+                             self.left, (self.left + self.width - 1) % chart.width,
+                             self.top, (self.top + self.height - 1) % chart.height,
+                             chart.width, chart.height));
+        }
+        outln!("{}", ".".repeat(self.width*2+2));
+        for y in self.top..self.top + self.height {
+            out!(".");
+            for x in self.left..self.left + self.width {
+                out!("{}", glyph(chart.at(x % chart.width, y % chart.height), chart.own));
+            }
+            outln!(".");
+        }
+        outln!("{}", ".".repeat(self.width*2+2));
+    }
+}
+
+// The chart squeezed into at most max x max cells, each showing the most
+// interesting thing in its scale x scale patch of ocean.  Returns the scale
+// and the rows.
+fn overview(chart: &Chart, max: usize) -> (usize, Vec<Vec<&Option<EType>>>) {
+    let scale = chart.width.div_ceil(max).max(chart.height.div_ceil(max));
+    let width = chart.width.div_ceil(scale);
+    let height = chart.height.div_ceil(scale);
+    let rows = (0..height).map(|oy| {
+        (0..width).map(|ox| {
+            let mut best = &None;
+            for y in oy*scale..((oy+1)*scale).min(chart.height) {
+                for x in ox*scale..((ox+1)*scale).min(chart.width) {
+                    let cell = chart.at(x, y);
+                    if priority(cell) > priority(best) {
+                        best = cell;
+                    }
+                }
+            }
+            best
+        }).collect()
+    }).collect();
+    (scale, rows)
+}
+
+/// Draw the whole chart squeezed into at most max x max cells.
+pub fn draw_overview(chart: &Chart, max: usize) {
+    let (scale, rows) = overview(chart, max);
+    let width = rows[0].len();
+
+    outln!("{}", tr!("overview", scale));
+    outln!("{}", ":".repeat(width*2+2));
+    for row in &rows {
+        out!(":");
+        for cell in row {
+            out!("{}", glyph(cell, chart.own));
+        }
+        outln!(":");
+    }
+    outln!("{}", ":".repeat(width*2+2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {set_ocean, Ocean};
+
+    fn chart(width: usize, height: usize, wrap: bool) -> Chart {
+        set_ocean(Ocean { width, height, wrap });
+        Chart::new(0)
+    }
+
+    fn corner(view: &Viewport) -> (usize, usize) {
+        (view.left, view.top)
+    }
+
+    #[test]
+    fn viewport_stays_inside_the_ocean() {
+        let chart = chart(50, 40, false);
+        let centred = |x, y| corner(&Viewport::centred(&chart, &Position { x, y }));
+        assert_eq!(centred(25, 20), (15, 10));
+        assert_eq!(centred(2, 3), (0, 0));
+        assert_eq!(centred(49, 39), (30, 20));
+        assert_eq!(centred(40, 5), (30, 0));
+        // A small ocean is shown whole.
+        let small = self::chart(15, 12, false);
+        let view = Viewport::centred(&small, &Position { x: 14, y: 11 });
+        assert_eq!((view.left, view.top, view.width, view.height), (0, 0, 15, 12));
+    }
+
+    #[test]
+    fn panning() {
+        let chart = chart(50, 40, false);
+        let mut view = Viewport::centred(&chart, &Position { x: 25, y: 20 });
+        view.pan(&chart, 1, 0);
+        assert_eq!(corner(&view), (25, 10));
+        view.pan(&chart, 1, 0);
+        assert_eq!(corner(&view), (30, 10));
+        view.pan(&chart, -1, 0);
+        assert_eq!(corner(&view), (20, 10));
+        view.pan(&chart, 0, 1);
+        assert_eq!(corner(&view), (20, 20));
+        view.pan(&chart, 0, -1);
+        assert_eq!(corner(&view), (20, 10));
+        view.pan(&chart, 0, -1);
+        assert_eq!(corner(&view), (20, 0));
+        view.pan(&chart, -1, 1);
+        assert_eq!(corner(&view), (10, 10));
+        view.pan(&chart, -1, -1);
+        assert_eq!(corner(&view), (0, 0));
+    }
+
+    #[test]
+    fn viewport_wraps_around() {
+        let chart = chart(50, 40, true);
+        let centred = |x, y| corner(&Viewport::centred(&chart, &Position { x, y }));
+        assert_eq!(centred(25, 20), (15, 10));
+        assert_eq!(centred(2, 3), (42, 33));
+        assert_eq!(centred(49, 39), (39, 29));
+        let mut view = Viewport::centred(&chart, &Position { x: 45, y: 5 });
+        assert_eq!(corner(&view), (35, 35));
+        view.pan(&chart, 1, 1);
+        assert_eq!(corner(&view), (45, 5));
+        view.pan(&chart, 1, 1);
+        assert_eq!(corner(&view), (5, 15));
+        view.pan(&chart, -1, -1);
+        view.pan(&chart, -1, -1);
+        view.pan(&chart, -1, -1);
+        assert_eq!(corner(&view), (25, 25));
+    }
+
+    #[test]
+    fn overview_of_a_large_ocean() {
+        let mut chart = chart(50, 30, false);
+        // Ships outrank islands when they share an overview cell.
+        chart.mark(&Position { x: 1, y: 1 }, EType::Island);
+        chart.mark(&Position { x: 4, y: 4 }, EType::Ship);
+        chart.mark(&Position { x: 5, y: 0 }, EType::Island);
+        chart.mark(&Position { x: 49, y: 29 }, EType::Mine);
+        // Five cells to a side fits fifty by thirty into ten by six.
+        let (scale, rows) = overview(&chart, 10);
+        assert_eq!(scale, 5);
+        assert_eq!(rows.len(), 6);
+        assert!(rows.iter().all(|row| row.len() == 10));
+        assert_eq!(*rows[0][0], Some(EType::Ship));
+        assert_eq!(*rows[0][1], Some(EType::Island));
+        assert_eq!(*rows[5][9], Some(EType::Mine));
+        assert_eq!(rows.iter().flatten().filter(|c| c.is_some()).count(), 3);
+        let shape = |width, height, max| {
+            let chart = self::chart(width, height, false);
+            let (scale, rows) = overview(&chart, max);
+            (scale, rows[0].len(), rows.len())
+        };
+        // Uneven oceans keep their shape, with a part cell at the end.
+        assert_eq!(shape(45, 12, 10), (5, 9, 3));
+        // An ocean that fits is drawn cell for cell.
+        assert_eq!(shape(20, 20, 20), (1, 20, 20));
+    }
+
+
+    #[test]
+    fn bearings() {
+        chart(20, 20, false);
+        let from = Position { x: 10, y: 10 };
+        let bearing = |x, y| bearing(&from, &Position { x, y });
+        assert_eq!(bearing(10, 10), "right here");
+        assert_eq!(bearing(10, 7), "3 cells north");
+        assert_eq!(bearing(13, 7), "3 cells northeast");
+        assert_eq!(bearing(11, 10), "1 cell east");
+        assert_eq!(bearing(12, 12), "2 cells southeast");
+        assert_eq!(bearing(10, 19), "9 cells south");
+        assert_eq!(bearing(5, 15), "5 cells southwest");
+        assert_eq!(bearing(0, 10), "10 cells west");
+        assert_eq!(bearing(9, 9), "1 cell northwest");
+        // Close enough to a diagonal counts as one.
+        assert_eq!(bearing(14, 8), "4 cells northeast");
+        assert_eq!(bearing(15, 8), "5 cells east");
+    }
+
+    #[test]
+    fn bearings_across_the_seam() {
+        chart(20, 20, true);
+        let bearing = |from: (usize, usize), to: (usize, usize)| {
+            bearing(&Position { x: from.0, y: from.1 }, &Position { x: to.0, y: to.1 })
+        };
+        assert_eq!(bearing((1, 10), (18, 10)), "3 cells west");
+        assert_eq!(bearing((18, 10), (1, 10)), "3 cells east");
+        assert_eq!(bearing((10, 0), (10, 19)), "1 cell north");
+        assert_eq!(bearing((19, 19), (0, 0)), "1 cell southeast");
+        assert_eq!(bearing((0, 18), (18, 1)), "3 cells southwest");
+        // Without wrapping, the long way is the only way.
+        self::chart(20, 20, false);
+        assert_eq!(bearing((1, 10), (18, 10)), "17 cells east");
+    }
+}